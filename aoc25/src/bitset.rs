@@ -0,0 +1,328 @@
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not},
+};
+
+const WORD_BITS: usize = u64::BITS as usize;
+const ON: char = '#';
+const OFF: char = '.';
+const DIAGRAM_START: char = '[';
+const DIAGRAM_END: char = ']';
+
+/// A growable set of bits packed into 64-bit words.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    len: usize,
+    words: Vec<u64>,
+}
+
+/// A set of up to `64 * WORDS` bits stored inline, so it is `Copy` and never allocates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FixedBitSet<const WORDS: usize> {
+    len: usize,
+    words: [u64; WORDS],
+}
+
+pub type BitSet64 = FixedBitSet<1>;
+pub type BitSet128 = FixedBitSet<2>;
+
+impl BitSet {
+    pub fn new(len: usize) -> Self {
+        BitSet {
+            len,
+            words: vec![0; len.div_ceil(WORD_BITS)],
+        }
+    }
+
+    pub fn push(&mut self, value: bool) {
+        if self.len == self.words.len() * WORD_BITS {
+            self.words.push(0);
+        }
+        self.len += 1;
+        self.set(self.len - 1, value);
+    }
+}
+
+impl FromIterator<bool> for BitSet {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut result = BitSet::default();
+        for value in iter {
+            result.push(value);
+        }
+        result
+    }
+}
+
+impl<const WORDS: usize> FixedBitSet<WORDS> {
+    pub const CAPACITY: usize = WORDS * WORD_BITS;
+
+    pub fn new(len: usize) -> Self {
+        assert!(
+            len <= Self::CAPACITY,
+            "length {} exceeds capacity {}",
+            len,
+            Self::CAPACITY
+        );
+        FixedBitSet {
+            len,
+            words: [0; WORDS],
+        }
+    }
+}
+
+impl<const WORDS: usize> Default for FixedBitSet<WORDS> {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl<const WORDS: usize> FromIterator<bool> for FixedBitSet<WORDS> {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut result = Self::default();
+        for value in iter {
+            assert!(result.len < Self::CAPACITY, "capacity exceeded");
+            result.len += 1;
+            result.set(result.len - 1, value);
+        }
+        result
+    }
+}
+
+macro_rules! impl_bit_set {
+    ($name:ident $(<const $words:ident: usize>)?) => {
+        impl$(<const $words: usize>)? $name$(<$words>)? {
+            pub fn len(&self) -> usize {
+                self.len
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.len == 0
+            }
+
+            pub fn get(&self, i: usize) -> bool {
+                self.check_index(i);
+                self.words[i / WORD_BITS] >> (i % WORD_BITS) & 1 == 1
+            }
+
+            pub fn set(&mut self, i: usize, value: bool) {
+                self.check_index(i);
+                let mask = 1 << (i % WORD_BITS);
+                if value {
+                    self.words[i / WORD_BITS] |= mask;
+                } else {
+                    self.words[i / WORD_BITS] &= !mask;
+                }
+            }
+
+            pub fn flip(&mut self, i: usize) {
+                self.check_index(i);
+                self.words[i / WORD_BITS] ^= 1 << (i % WORD_BITS);
+            }
+
+            pub fn clear(&mut self) {
+                self.words.iter_mut().for_each(|word| *word = 0);
+            }
+
+            pub fn is_zero(&self) -> bool {
+                self.words.iter().all(|word| *word == 0)
+            }
+
+            /// The number of set bits.
+            pub fn count_ones(&self) -> usize {
+                self.words.iter().map(|word| word.count_ones() as usize).sum()
+            }
+
+            /// Iterates over the indices of the set bits in ascending order.
+            pub fn ones_iter(&self) -> impl Iterator<Item = usize> + '_ {
+                self.words.iter().enumerate().flat_map(|(w, word)| {
+                    let mut word = *word;
+                    std::iter::from_fn(move || {
+                        if word == 0 {
+                            return None;
+                        }
+                        let bit = word.trailing_zeros() as usize;
+                        word &= word - 1;
+                        Some(w * WORD_BITS + bit)
+                    })
+                })
+            }
+
+            pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+                (0..self.len).map(|i| self.get(i))
+            }
+
+            fn check_index(&self, i: usize) {
+                assert!(i < self.len, "index {} out of bounds for length {}", i, self.len);
+            }
+
+            fn clear_unused_bits(&mut self) {
+                let used = self.len % WORD_BITS;
+                let last = self.len / WORD_BITS;
+                if used != 0 {
+                    self.words[last] &= (1 << used) - 1;
+                }
+                self.words[last + (used != 0) as usize..]
+                    .iter_mut()
+                    .for_each(|word| *word = 0);
+            }
+        }
+
+        impl_bit_set!(@op $name $(<const $words: usize>)?, BitXor, bitxor, BitXorAssign, bitxor_assign, ^=);
+        impl_bit_set!(@op $name $(<const $words: usize>)?, BitAnd, bitand, BitAndAssign, bitand_assign, &=);
+        impl_bit_set!(@op $name $(<const $words: usize>)?, BitOr, bitor, BitOrAssign, bitor_assign, |=);
+
+        impl$(<const $words: usize>)? Not for &$name$(<$words>)? {
+            type Output = $name$(<$words>)?;
+
+            fn not(self) -> Self::Output {
+                let mut result = self.clone();
+                result.words.iter_mut().for_each(|word| *word = !*word);
+                result.clear_unused_bits();
+                result
+            }
+        }
+
+        /// Compares bit by bit from index 0, like a `Vec<bool>`, so a set sorts before any longer
+        /// set it is a prefix of.
+        impl$(<const $words: usize>)? Ord for $name$(<$words>)? {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.iter().cmp(other.iter())
+            }
+        }
+
+        impl$(<const $words: usize>)? PartialOrd for $name$(<$words>)? {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        /// Parses the `[.##.]` diagram notation. The brackets are optional.
+        impl$(<const $words: usize>)? From<&str> for $name$(<$words>)? {
+            fn from(value: &str) -> Self {
+                let value = value.strip_prefix(DIAGRAM_START).unwrap_or(value);
+                let value = value.strip_suffix(DIAGRAM_END).unwrap_or(value);
+                value
+                    .chars()
+                    .map(|ch| match ch {
+                        ON => true,
+                        OFF => false,
+                        _ => panic!("invalid bit: {}", ch),
+                    })
+                    .collect()
+            }
+        }
+
+        impl$(<const $words: usize>)? Display for $name$(<$words>)? {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", DIAGRAM_START)?;
+                for value in self.iter() {
+                    write!(f, "{}", if value { ON } else { OFF })?;
+                }
+                write!(f, "{}", DIAGRAM_END)
+            }
+        }
+    };
+
+    (@op $name:ident $(<const $words:ident: usize>)?, $op:ident, $op_fn:ident, $assign:ident, $assign_fn:ident, $token:tt) => {
+        impl$(<const $words: usize>)? $assign<&$name$(<$words>)?> for $name$(<$words>)? {
+            fn $assign_fn(&mut self, rhs: &$name$(<$words>)?) {
+                assert_eq!(self.len, rhs.len, "length mismatch");
+                for (a, b) in self.words.iter_mut().zip(&rhs.words) {
+                    *a $token b;
+                }
+            }
+        }
+
+        impl$(<const $words: usize>)? $op<&$name$(<$words>)?> for &$name$(<$words>)? {
+            type Output = $name$(<$words>)?;
+
+            fn $op_fn(self, rhs: &$name$(<$words>)?) -> Self::Output {
+                let mut result = self.clone();
+                result $token rhs;
+                result
+            }
+        }
+    };
+}
+
+impl_bit_set!(BitSet);
+impl_bit_set!(FixedBitSet<const WORDS: usize>);
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn diagram_round_trip() {
+        let lights = BitSet::from("[.##.]");
+
+        assert_eq!(lights.len(), 4);
+        assert!(!lights.get(0));
+        assert!(lights.get(1));
+        assert_eq!(lights.to_string(), "[.##.]");
+        assert_eq!(BitSet::from("#.#"), BitSet::from("[#.#]"));
+    }
+
+    #[test]
+    fn operators() {
+        let a = BitSet::from("##..");
+        let b = BitSet::from(".#.#");
+
+        assert_eq!(&a ^ &b, BitSet::from("#..#"));
+        assert_eq!(&a & &b, BitSet::from(".#.."));
+        assert_eq!(&a | &b, BitSet::from("##.#"));
+        assert_eq!(!&a, BitSet::from("..##"));
+    }
+
+    #[test]
+    fn count_and_iterate_ones() {
+        let mut bits = BitSet::new(200);
+        for i in [0, 63, 64, 130, 199] {
+            bits.set(i, true);
+        }
+
+        assert_eq!(bits.count_ones(), 5);
+        assert_eq!(
+            bits.ones_iter().collect::<Vec<_>>(),
+            vec![0, 63, 64, 130, 199]
+        );
+
+        bits.flip(63);
+        assert_eq!(bits.count_ones(), 4);
+        assert!(!(!&bits).get(0));
+        assert_eq!((!&bits).count_ones(), 196);
+    }
+
+    #[test]
+    fn fixed_bit_set() {
+        let mut lights = BitSet64::from("[...#.]");
+        lights ^= &BitSet64::from("[#..#.]");
+
+        assert_eq!(lights.to_string(), "[#....]");
+        assert_eq!((!&lights).count_ones(), 4);
+        assert_eq!(BitSet128::CAPACITY, 128);
+    }
+
+    #[test]
+    #[should_panic]
+    fn fixed_bit_set_capacity() {
+        BitSet64::new(65);
+    }
+
+    #[test]
+    fn hash_and_ord() {
+        let mut seen = HashSet::new();
+        assert!(seen.insert(BitSet64::from("#.")));
+        assert!(!seen.insert(BitSet64::from("#.")));
+        assert!(BitSet::from("#.") > BitSet::from(".."));
+
+        // The first bit decides, not the packed words, where bit 1 outweighs bit 0
+        assert!(BitSet::from("#.") > BitSet::from(".#"));
+        assert!(BitSet64::from("#.") > BitSet64::from(".#"));
+        assert!(BitSet::from("#") < BitSet::from("#."));
+        assert!(BitSet::from("#") > BitSet::from(".."));
+    }
+}
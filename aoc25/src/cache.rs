@@ -0,0 +1,364 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    fs,
+    hash::{Hash, Hasher},
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::char_grid::CharGrid;
+
+const EXTENSION: &str = "cache";
+
+/// Memoizes named computations on disk, keyed by a hash of their input.
+///
+/// Each entry is stored in its own file alongside the version tag and input hash it was computed
+/// with. An entry is recomputed when either of those changes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Cache { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the cached result of `name` for `input`, running `compute` and storing its result
+    /// if there is no valid entry. Entries are keyed by a hash of the input's bytes.
+    pub fn get_or_compute<I, T, F>(
+        &self,
+        name: &str,
+        version: &str,
+        input: &I,
+        compute: F,
+    ) -> io::Result<T>
+    where
+        I: AsRef<[u8]> + ?Sized,
+        T: CacheValue,
+        F: FnOnce() -> T,
+    {
+        let key = CacheKey::new(version, input);
+
+        if let Some(value) = self.get(name, &key)? {
+            return Ok(value);
+        }
+
+        let value = compute();
+        self.put(name, &key, &value)?;
+        Ok(value)
+    }
+
+    pub fn invalidate(&self, name: &str) -> io::Result<()> {
+        match fs::remove_file(self.path_of(name)) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn clear(&self) -> io::Result<()> {
+        match fs::remove_dir_all(&self.dir) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    fn get<T: CacheValue>(&self, name: &str, key: &CacheKey) -> io::Result<Option<T>> {
+        let contents = match fs::read_to_string(self.path_of(name)) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        let Some((header, payload)) = contents.split_once('\n') else {
+            return Ok(None);
+        };
+
+        if header != key.to_string() {
+            // Stale entry
+            return Ok(None);
+        }
+
+        Ok(T::decode(payload))
+    }
+
+    fn put<T: CacheValue>(&self, name: &str, key: &CacheKey, value: &T) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.path_of(name), format!("{}\n{}", key, value.encode()))
+    }
+
+    fn path_of(&self, name: &str) -> PathBuf {
+        let file_name: String = name
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' => c,
+                _ => '_',
+            })
+            .collect();
+        self.dir.join(format!("{}.{}", file_name, EXTENSION))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    version: String,
+    input_hash: u64,
+}
+
+impl CacheKey {
+    fn new<I: AsRef<[u8]> + ?Sized>(version: &str, input: &I) -> Self {
+        let mut hasher = StableHasher::default();
+        hasher.write(input.as_ref());
+        CacheKey {
+            version: version.replace(['\t', '\n'], " "),
+            input_hash: hasher.finish(),
+        }
+    }
+}
+
+impl Display for CacheKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\t{:016x}", self.version, self.input_hash)
+    }
+}
+
+/// FNV-1a hasher whose output does not change between runs or toolchains, unlike
+/// `DefaultHasher`. That only holds for bytes passed to [`Hasher::write`] directly, as std's `Hash`
+/// impls may feed it different bytes on other platforms or releases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StableHasher(u64);
+
+impl StableHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+}
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        StableHasher(Self::OFFSET_BASIS)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+}
+
+/// A value that can be stored in a [`Cache`].
+pub trait CacheValue: Sized {
+    fn encode(&self) -> String;
+
+    /// Returns `None` if the payload is malformed, in which case the value is recomputed.
+    fn decode(payload: &str) -> Option<Self>;
+}
+
+impl CacheValue for String {
+    fn encode(&self) -> String {
+        self.clone()
+    }
+
+    fn decode(payload: &str) -> Option<Self> {
+        Some(payload.to_string())
+    }
+}
+
+impl CacheValue for CharGrid {
+    fn encode(&self) -> String {
+        self.to_raw()
+    }
+
+    fn decode(payload: &str) -> Option<Self> {
+        if payload.is_empty() {
+            return None;
+        }
+        Some(payload.into())
+    }
+}
+
+/// Stored as the number of values on the first line followed by one escaped value per line, so
+/// empty values survive the round trip.
+impl<T: CacheValue> CacheValue for Vec<T> {
+    fn encode(&self) -> String {
+        let mut lines = vec![self.len().to_string()];
+        lines.extend(
+            self.iter()
+                .map(|value| value.encode().replace('\\', "\\\\").replace('\n', "\\n")),
+        );
+        lines.join("\n")
+    }
+
+    fn decode(payload: &str) -> Option<Self> {
+        let mut lines = payload.split('\n');
+        let len: usize = lines.next()?.parse().ok()?;
+        let values: Vec<T> = lines
+            .map(|line| T::decode(&unescape_line(line)))
+            .collect::<Option<_>>()?;
+
+        (values.len() == len).then_some(values)
+    }
+}
+
+fn unescape_line(line: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+
+    result
+}
+
+macro_rules! impl_cache_value_for_primitive {
+    ($($t:ty),*) => {
+        $(
+            impl CacheValue for $t {
+                fn encode(&self) -> String {
+                    self.to_string()
+                }
+
+                fn decode(payload: &str) -> Option<Self> {
+                    payload.parse().ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_cache_value_for_primitive!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, bool, char
+);
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    fn temp_cache(name: &str) -> Cache {
+        let dir = std::env::temp_dir().join(format!("aoc25-cache-{}-{}", name, std::process::id()));
+        let cache = Cache::new(dir);
+        cache.clear().unwrap();
+        cache
+    }
+
+    #[test]
+    fn get_or_compute_memoizes() {
+        let cache = temp_cache("memoizes");
+        let calls = Cell::new(0);
+        let compute = || {
+            calls.set(calls.get() + 1);
+            42u64
+        };
+
+        assert_eq!(
+            cache
+                .get_or_compute("answer", "1", "input", compute)
+                .unwrap(),
+            42
+        );
+        assert_eq!(
+            cache
+                .get_or_compute("answer", "1", "input", compute)
+                .unwrap(),
+            42
+        );
+        assert_eq!(calls.get(), 1);
+
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn get_or_compute_invalidates_on_input_change() {
+        let cache = temp_cache("input");
+
+        assert_eq!(
+            cache.get_or_compute("len", "1", "abc", || 3usize).unwrap(),
+            3
+        );
+        assert_eq!(
+            cache.get_or_compute("len", "1", "abcd", || 4usize).unwrap(),
+            4
+        );
+        assert_eq!(
+            cache.get_or_compute("len", "1", "abcd", || 0usize).unwrap(),
+            4
+        );
+
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn get_or_compute_invalidates_on_version_change() {
+        let cache = temp_cache("version");
+
+        assert_eq!(cache.get_or_compute("n", "1", "7", || 1i32).unwrap(), 1);
+        assert_eq!(cache.get_or_compute("n", "2", "7", || 2i32).unwrap(), 2);
+
+        cache.invalidate("n").unwrap();
+        assert_eq!(cache.get_or_compute("n", "2", "7", || 3i32).unwrap(), 3);
+
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn char_grid_round_trip() {
+        let cache = temp_cache("grid");
+        let grid: CharGrid = "#.#\n.#.".into();
+
+        let stored = cache
+            .get_or_compute("grid", "1", "seed", || grid.clone())
+            .unwrap();
+        let loaded: CharGrid = cache
+            .get_or_compute("grid", "1", "seed", CharGrid::default)
+            .unwrap();
+
+        assert_eq!(stored, grid);
+        assert_eq!(loaded, grid);
+
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn vec_round_trip() {
+        let values = vec!["a\nb".to_string(), "c\\n".to_string(), String::new()];
+        let decoded = Vec::<String>::decode(&values.encode()).unwrap();
+        assert_eq!(decoded, values);
+
+        for values in [vec![], vec![String::new()], vec![String::new(); 2]] {
+            assert_eq!(Vec::<String>::decode(&values.encode()), Some(values));
+        }
+        assert_eq!(Vec::<String>::decode("3\na"), None);
+    }
+
+    #[test]
+    fn stable_hasher_matches_fnv_1a() {
+        let mut hasher = StableHasher::default();
+        assert_eq!(hasher.finish(), 0xcbf2_9ce4_8422_2325);
+
+        // The published FNV-1a 64 bit hash of "hello"
+        hasher.write(b"hello");
+        assert_eq!(hasher.finish(), 0xa430_d846_80aa_bd0b);
+    }
+}
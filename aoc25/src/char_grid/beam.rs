@@ -0,0 +1,371 @@
+use std::collections::HashMap;
+
+use crate::{
+    char_grid::{CharGrid, Point},
+    direction::Direction,
+};
+
+/// A diagonal mirror, named after the char that draws it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Mirror {
+    /// `/`, turning a beam heading right to head up
+    Slash,
+    /// `\`, turning a beam heading right to head down
+    Backslash,
+}
+
+impl Mirror {
+    pub fn reflect(self, heading: Direction) -> Direction {
+        match (self, heading.is_horizontal()) {
+            (Mirror::Slash, true) | (Mirror::Backslash, false) => heading.turn_left(),
+            (Mirror::Slash, false) | (Mirror::Backslash, true) => heading.turn_right(),
+        }
+    }
+}
+
+/// What a cell does to a beam passing through it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Behavior {
+    /// Keeps the beam going the same way
+    #[default]
+    Pass,
+    /// Sends the beam out in each of the directions. A beam already travelling along one of them,
+    /// either way, passes straight through instead
+    Split(&'static [Direction]),
+    /// Like `Split`, but ends a beam already travelling along one of the directions instead of
+    /// letting it through
+    SplitOrAbsorb(&'static [Direction]),
+    Reflect(Mirror),
+    /// Ends the beam
+    Absorb,
+    /// Sends the beam out in one direction, whichever way it came in
+    Redirect(Direction),
+}
+
+impl Behavior {
+    /// The directions a beam heading `heading` leaves the cell in.
+    pub fn outgoing(self, heading: Direction) -> Vec<Direction> {
+        match self {
+            Behavior::Pass => vec![heading],
+            Behavior::Split(directions) if is_along(directions, heading) => vec![heading],
+            Behavior::SplitOrAbsorb(directions) if is_along(directions, heading) => vec![],
+            Behavior::Split(directions) | Behavior::SplitOrAbsorb(directions) => {
+                directions.to_vec()
+            }
+            Behavior::Reflect(mirror) => vec![mirror.reflect(heading)],
+            Behavior::Absorb => vec![],
+            Behavior::Redirect(direction) => vec![direction],
+        }
+    }
+}
+
+fn is_along(directions: &[Direction], heading: Direction) -> bool {
+    directions.contains(&heading) || directions.contains(&heading.opposite())
+}
+
+/// The behavior of each char. Chars without a rule get the default behavior, `Pass` unless set.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BeamRules {
+    rules: HashMap<char, Behavior>,
+    default: Behavior,
+}
+
+impl BeamRules {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, ch: char, behavior: Behavior) -> Self {
+        self.rules.insert(ch, behavior);
+        self
+    }
+
+    pub fn with_default(mut self, behavior: Behavior) -> Self {
+        self.default = behavior;
+        self
+    }
+
+    pub fn get(&self, ch: char) -> Behavior {
+        self.rules.get(&ch).copied().unwrap_or(self.default)
+    }
+
+    /// Whether a char has its own rule rather than the default.
+    pub fn has_rule(&self, ch: char) -> bool {
+        self.rules.contains_key(&ch)
+    }
+}
+
+/// A beam in a cell, heading the way it will leave unless the cell turns it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Beam {
+    pub pos: Point,
+    pub heading: Direction,
+}
+
+/// What happened to the beams so far.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BeamStats {
+    pub steps: usize,
+    /// Beams that split in two or more
+    pub splits: usize,
+    /// Beam arrivals at each char with its own rule
+    pub hits: HashMap<char, usize>,
+    pub absorbed: usize,
+    /// Beams that left the grid
+    pub exits: usize,
+    /// Beams dropped because a beam had already been in the same cell heading the same way. This
+    /// covers beams merging as well as beams going round a loop
+    pub merged: usize,
+}
+
+/// Moves beams across a grid one cell per step following `BeamRules`. A cell and heading are only
+/// ever followed once, so beams that meet merge, and every run ends even when the beams loop.
+#[derive(Debug, Clone)]
+pub struct BeamEngine<'a> {
+    grid: &'a CharGrid,
+    rules: BeamRules,
+    beams: Vec<Beam>,
+    /// A bit for each heading a beam has had in each cell
+    seen: Vec<u8>,
+    visits: Vec<usize>,
+    stats: BeamStats,
+}
+
+impl<'a> BeamEngine<'a> {
+    pub fn new(grid: &'a CharGrid, rules: BeamRules) -> Self {
+        BeamEngine {
+            grid,
+            rules,
+            beams: vec![],
+            seen: vec![0; grid.size()],
+            visits: vec![0; grid.size()],
+            stats: BeamStats::default(),
+        }
+    }
+
+    /// Adds a beam in a cell. The cell acts on it at the next step.
+    pub fn emit(&mut self, pos: Point, heading: Direction) {
+        if let Some(beam) = self.arrive(pos, heading) {
+            self.beams.push(beam);
+        }
+    }
+
+    /// Moves every beam on by one cell, returning whether any beams are left.
+    pub fn step(&mut self) -> bool {
+        if self.beams.is_empty() {
+            return false;
+        }
+
+        let mut next = vec![];
+        for beam in std::mem::take(&mut self.beams) {
+            let ch = self.grid.get(beam.pos.0, beam.pos.1);
+            let outgoing = self.rules.get(ch).outgoing(beam.heading);
+
+            match outgoing.len() {
+                0 => self.stats.absorbed += 1,
+                1 => {}
+                _ => self.stats.splits += 1,
+            }
+
+            for heading in outgoing {
+                match self.grid.step(beam.pos, heading) {
+                    Some(pos) => next.extend(self.arrive(pos, heading)),
+                    None => self.stats.exits += 1,
+                }
+            }
+        }
+
+        self.beams = next;
+        self.stats.steps += 1;
+        !self.beams.is_empty()
+    }
+
+    /// Steps until every beam has stopped.
+    pub fn run(&mut self) -> &BeamStats {
+        while self.step() {}
+        &self.stats
+    }
+
+    /// The beams still moving.
+    pub fn beams(&self) -> &[Beam] {
+        &self.beams
+    }
+
+    pub fn stats(&self) -> &BeamStats {
+        &self.stats
+    }
+
+    /// How many times a beam has arrived in a cell, counting beams that were merged there.
+    pub fn visits(&self, x: usize, y: usize) -> usize {
+        self.visits[y * self.grid.width() + x]
+    }
+
+    /// The number of cells any beam has been in.
+    pub fn visited_count(&self) -> usize {
+        self.visits.iter().filter(|&&visits| visits > 0).count()
+    }
+
+    /// A copy of the grid with `value` in every visited cell that has no rule of its own.
+    pub fn trace(&self, value: char) -> CharGrid {
+        let mut grid = self.grid.clone();
+        for (x, y) in self.grid.coordinates_iter() {
+            if self.visits(x, y) > 0 && !self.rules.has_rule(self.grid.get(x, y)) {
+                grid.set(x, y, value);
+            }
+        }
+        grid
+    }
+
+    fn arrive(&mut self, pos: Point, heading: Direction) -> Option<Beam> {
+        let index = pos.1 * self.grid.width() + pos.0;
+        let ch = self.grid.get(pos.0, pos.1);
+
+        self.visits[index] += 1;
+        if self.rules.has_rule(ch) {
+            *self.stats.hits.entry(ch).or_default() += 1;
+        }
+
+        let bit = 1 << heading as u8;
+        if self.seen[index] & bit != 0 {
+            self.stats.merged += 1;
+            return None;
+        }
+        self.seen[index] |= bit;
+
+        Some(Beam { pos, heading })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_grid_eq;
+
+    const CONTRAPTION: &str = r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....";
+
+    fn contraption_rules() -> BeamRules {
+        BeamRules::new()
+            .with('/', Behavior::Reflect(Mirror::Slash))
+            .with('\\', Behavior::Reflect(Mirror::Backslash))
+            .with('|', Behavior::Split(&[Direction::Up, Direction::Down]))
+            .with('-', Behavior::Split(&[Direction::Left, Direction::Right]))
+    }
+
+    #[test]
+    fn behaviors() {
+        assert_eq!(Mirror::Slash.reflect(Direction::Right), Direction::Up);
+        assert_eq!(Mirror::Slash.reflect(Direction::Down), Direction::Left);
+        assert_eq!(Mirror::Backslash.reflect(Direction::Right), Direction::Down);
+        assert_eq!(Mirror::Backslash.reflect(Direction::Up), Direction::Left);
+
+        let split = Behavior::Split(&[Direction::Up, Direction::Down]);
+        assert_eq!(
+            split.outgoing(Direction::Left),
+            vec![Direction::Up, Direction::Down]
+        );
+        assert_eq!(split.outgoing(Direction::Down), vec![Direction::Down]);
+        assert!(Behavior::Absorb.outgoing(Direction::Up).is_empty());
+
+        let split = Behavior::SplitOrAbsorb(&[Direction::Left, Direction::Right]);
+        assert_eq!(
+            split.outgoing(Direction::Down),
+            vec![Direction::Left, Direction::Right]
+        );
+        assert!(split.outgoing(Direction::Left).is_empty());
+    }
+
+    #[test]
+    fn mirrors_and_splitters() {
+        let grid = CharGrid::from(CONTRAPTION);
+        let mut engine = BeamEngine::new(&grid, contraption_rules());
+        engine.emit((0, 0), Direction::Right);
+        let stats = engine.run().clone();
+
+        assert_eq!(engine.visited_count(), 46);
+        assert!(engine.beams().is_empty());
+        assert!(stats.merged > 0);
+        assert_eq!(engine.visits(9, 0), 0);
+        assert_grid_eq!(
+            engine.trace('#'),
+            CharGrid::from(
+                r"#|###\....
+|#-.\#....
+.#...|-###
+.#...##.|.
+.#...##...
+.#...##..\
+.#../#\\..
+#-#-/##|..
+.|####-|.\
+.#//.|.#.."
+            )
+        );
+    }
+
+    #[test]
+    fn loops_end() {
+        let grid = CharGrid::from("/.\\\n.#.\n\\./");
+        let rules = contraption_rules().with('#', Behavior::Absorb);
+        let mut engine = BeamEngine::new(&grid, rules);
+        engine.emit((1, 0), Direction::Right);
+        let stats = engine.run();
+
+        assert_eq!(stats.merged, 1);
+        assert_eq!(stats.exits, 0);
+        assert_eq!(stats.absorbed, 0);
+        assert_eq!(engine.visited_count(), 8);
+    }
+
+    #[test]
+    fn falling_splitters() {
+        let grid = CharGrid::from("..S..\n.....\n..^..\n.....\n.^.^.\n.....");
+        let rules = BeamRules::new()
+            .with_default(Behavior::Redirect(Direction::Down))
+            .with('^', Behavior::Split(&[Direction::Left, Direction::Right]));
+        let mut engine = BeamEngine::new(&grid, rules);
+        engine.emit((2, 0), Direction::Down);
+        let stats = engine.run();
+
+        assert_eq!(stats.splits, 3);
+        assert_eq!(stats.hits[&'^'], 3);
+        assert_eq!(stats.exits, 3);
+        // The two middle beams fall into the same cell
+        assert_eq!(stats.merged, 1);
+        assert_eq!(engine.visits(2, 4), 2);
+    }
+
+    #[test]
+    fn adjacent_splitters() {
+        let grid = CharGrid::from("...S...\n.......\n..^^...\n.......");
+        let run = |splitter| {
+            let rules = BeamRules::new()
+                .with_default(Behavior::Redirect(Direction::Down))
+                .with('^', splitter);
+            let mut engine = BeamEngine::new(&grid, rules);
+            engine.emit((3, 0), Direction::Down);
+            engine.run().clone()
+        };
+
+        // The beam split to the left runs into the next splitter sideways
+        let passed = run(Behavior::Split(&[Direction::Left, Direction::Right]));
+        assert_eq!((passed.splits, passed.absorbed, passed.exits), (1, 0, 2));
+
+        let absorbed = run(Behavior::SplitOrAbsorb(&[
+            Direction::Left,
+            Direction::Right,
+        ]));
+        assert_eq!(
+            (absorbed.splits, absorbed.absorbed, absorbed.exits),
+            (1, 1, 1)
+        );
+    }
+}
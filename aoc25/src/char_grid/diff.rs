@@ -0,0 +1,342 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::char_grid::CharGrid;
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const RESET: &str = "\x1b[0m";
+/// Shown for cells that only exist in one of the grids
+const MISSING: char = ' ';
+const SIDE_BY_SIDE_GAP: &str = " | ";
+/// Rows and columns of unchanged cells kept around the changes when printing a diff
+const CONTEXT: usize = 2;
+/// Changes listed individually before the rest are summarized
+const MAX_LISTED: usize = 10;
+
+/// A cell that differs between two grids. A side is `None` when the cell lies outside that grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CellChange {
+    pub x: usize,
+    pub y: usize,
+    pub old: Option<char>,
+    pub new: Option<char>,
+}
+
+impl Display for CellChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let show = |ch: Option<char>| ch.map_or("none".to_string(), |ch| format!("{:?}", ch));
+        write!(
+            f,
+            "({}, {}): {} -> {}",
+            self.x,
+            self.y,
+            show(self.old),
+            show(self.new)
+        )
+    }
+}
+
+/// The cells that differ between an old and a new grid, in row-major order. Only contents are
+/// compared, not display settings such as axes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GridDiff<'a> {
+    old: &'a CharGrid,
+    new: &'a CharGrid,
+    changes: Vec<CellChange>,
+}
+
+impl<'a> GridDiff<'a> {
+    pub fn new(old: &'a CharGrid, new: &'a CharGrid) -> Self {
+        let width = old.width().max(new.width());
+        let height = old.height().max(new.height());
+
+        let changes = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| CellChange {
+                x,
+                y,
+                old: cell(old, x, y),
+                new: cell(new, x, y),
+            })
+            .filter(|change| change.old != change.new)
+            .collect();
+
+        GridDiff { old, new, changes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    pub fn changes(&self) -> &[CellChange] {
+        &self.changes
+    }
+
+    /// The smallest `(min_x, min_y, max_x, max_y)` box holding every change, inclusive.
+    pub fn bounds(&self) -> Option<(usize, usize, usize, usize)> {
+        let first = self.changes.first()?;
+        Some(self.changes.iter().fold(
+            (first.x, first.y, first.x, first.y),
+            |(min_x, min_y, max_x, max_y), change| {
+                (
+                    min_x.min(change.x),
+                    min_y.min(change.y),
+                    max_x.max(change.x),
+                    max_y.max(change.y),
+                )
+            },
+        ))
+    }
+
+    /// The new grid with changed cells in green, and cells that disappeared shown in red with
+    /// their old char.
+    pub fn render_overlay(&self) -> String {
+        self.render(self.full_area(), |x, y, changed| {
+            match cell(self.new, x, y) {
+                Some(ch) if changed => colored(GREEN, ch),
+                Some(ch) => ch.to_string(),
+                None => colored(RED, cell(self.old, x, y).unwrap_or(MISSING)),
+            }
+        })
+    }
+
+    /// The old grid next to the new one, with changed cells in red on the left and green on the
+    /// right.
+    pub fn render_side_by_side(&self) -> String {
+        self.render_side_by_side_area(self.full_area())
+    }
+
+    fn render_side_by_side_area(&self, area: Option<(usize, usize, usize, usize)>) -> String {
+        let side = |grid: &'a CharGrid, color: &'static str| {
+            self.render(area, move |x, y, changed| {
+                let ch = cell(grid, x, y).unwrap_or(MISSING);
+                if changed {
+                    colored(color, ch)
+                } else {
+                    ch.to_string()
+                }
+            })
+        };
+
+        side(self.old, RED)
+            .lines()
+            .zip(side(self.new, GREEN).lines())
+            .map(|(old, new)| format!("{}{}{}\n", old, SIDE_BY_SIDE_GAP, new))
+            .collect()
+    }
+
+    /// The inclusive area covering both grids, or `None` if there are no cells to cover.
+    fn full_area(&self) -> Option<(usize, usize, usize, usize)> {
+        let width = self.old.width().max(self.new.width());
+        let height = self.old.height().max(self.new.height());
+        if width == 0 || height == 0 {
+            return None;
+        }
+        Some((0, 0, width - 1, height - 1))
+    }
+
+    /// Renders the inclusive area one row per line, with `render_cell` given each coordinate and
+    /// whether it changed. Renders nothing without an area.
+    fn render(
+        &self,
+        area: Option<(usize, usize, usize, usize)>,
+        render_cell: impl Fn(usize, usize, bool) -> String,
+    ) -> String {
+        let Some((min_x, min_y, max_x, max_y)) = area else {
+            return String::new();
+        };
+        let mut changes = self.changes.iter().peekable();
+        let mut output = String::new();
+
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                // Changes are sorted row-major, so skip any left of the area
+                while changes
+                    .next_if(|change| (change.y, change.x) < (y, x))
+                    .is_some()
+                {}
+                let changed = changes.next_if(|change| (change.x, change.y) == (x, y));
+                output.push_str(&render_cell(x, y, changed.is_some()));
+            }
+            output.push('\n');
+        }
+
+        output
+    }
+}
+
+/// Lists the first few changes, then shows both grids side by side, cropped to the changes.
+impl Display for GridDiff<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (Some((min_x, min_y, max_x, max_y)), Some((_, _, width, height))) =
+            (self.bounds(), self.full_area())
+        else {
+            return writeln!(f, "grids are equal");
+        };
+
+        writeln!(f, "cells differing: {}", self.len())?;
+        for change in self.changes.iter().take(MAX_LISTED) {
+            writeln!(f, "  {}", change)?;
+        }
+        if self.len() > MAX_LISTED {
+            writeln!(f, "  ... and {} more", self.len() - MAX_LISTED)?;
+        }
+
+        let area = (
+            min_x.saturating_sub(CONTEXT),
+            min_y.saturating_sub(CONTEXT),
+            (max_x + CONTEXT).min(width),
+            (max_y + CONTEXT).min(height),
+        );
+        writeln!(
+            f,
+            "old | new, columns {}..={}, rows {}..={}:",
+            area.0, area.2, area.1, area.3
+        )?;
+        write!(f, "{}", self.render_side_by_side_area(Some(area)))
+    }
+}
+
+impl CharGrid {
+    /// The cells that differ between this grid and `other`, treating this one as the old grid.
+    pub fn diff<'a>(&'a self, other: &'a CharGrid) -> GridDiff<'a> {
+        GridDiff::new(self, other)
+    }
+}
+
+fn cell(grid: &CharGrid, x: usize, y: usize) -> Option<char> {
+    (x < grid.width() && y < grid.height()).then(|| grid.get(x, y))
+}
+
+fn colored(color: &str, ch: char) -> String {
+    format!("{}{}{}", color, ch, RESET)
+}
+
+/// Asserts two `CharGrid`s have the same contents, printing only the cells that differ on failure.
+#[macro_export]
+macro_rules! assert_grid_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                let diff = $crate::char_grid::CharGrid::diff(left, right);
+                if !diff.is_empty() {
+                    panic!("assertion `left == right` failed: grids differ\n{}", diff);
+                }
+            }
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                let diff = $crate::char_grid::CharGrid::diff(left, right);
+                if !diff.is_empty() {
+                    panic!(
+                        "assertion `left == right` failed: {}\n{}",
+                        format_args!($($arg)+),
+                        diff
+                    );
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strip_colors(text: &str) -> String {
+        text.replace(RED, "").replace(GREEN, "").replace(RESET, "")
+    }
+
+    #[test]
+    fn changed_cells() {
+        let old = CharGrid::from("..@\n.@.\n...");
+        let new = CharGrid::from("..x\n.@.\n.@.");
+        let diff = old.diff(&new);
+
+        assert_eq!(
+            diff.changes(),
+            [
+                CellChange {
+                    x: 2,
+                    y: 0,
+                    old: Some('@'),
+                    new: Some('x')
+                },
+                CellChange {
+                    x: 1,
+                    y: 2,
+                    old: Some('.'),
+                    new: Some('@')
+                },
+            ]
+        );
+        assert_eq!(diff.bounds(), Some((1, 0, 2, 2)));
+        assert!(old.diff(&old).is_empty());
+    }
+
+    #[test]
+    fn different_sizes() {
+        let old = CharGrid::from("ab\ncd");
+        let new = CharGrid::from("abc");
+        let diff = old.diff(&new);
+
+        assert_eq!(diff.len(), 3);
+        assert_eq!(diff.changes()[0].old, None);
+        assert_eq!(diff.changes()[0].new, Some('c'));
+        assert_eq!(diff.changes()[1].new, None);
+        assert_eq!(strip_colors(&diff.render_overlay()), "abc\ncd \n");
+    }
+
+    #[test]
+    fn renderers_highlight_changes() {
+        let old = CharGrid::from("ab");
+        let new = CharGrid::from("aX");
+        let diff = old.diff(&new);
+
+        assert_eq!(diff.render_overlay(), format!("a{}X{}\n", GREEN, RESET));
+        assert_eq!(
+            diff.render_side_by_side(),
+            format!("a{}b{} | a{}X{}\n", RED, RESET, GREEN, RESET)
+        );
+
+        let empty = CharGrid::default();
+        assert_eq!(empty.diff(&empty).render_overlay(), "");
+        assert_eq!(empty.diff(&empty).render_side_by_side(), "");
+    }
+
+    #[test]
+    fn display_is_cropped() {
+        let old = CharGrid::with_default_char(20, 20, '.');
+        let mut new = old.clone();
+        new.set(10, 10, '#');
+        let output = strip_colors(&old.diff(&new).to_string());
+
+        assert!(output.starts_with("cells differing: 1\n  (10, 10): '.' -> '#'\n"));
+        assert!(output.contains("columns 8..=12, rows 8..=12"));
+        assert!(output.contains("\n..... | ..#..\n"));
+        assert_eq!(output.lines().count(), 3 + 5);
+    }
+
+    #[test]
+    fn assert_grid_eq_passes() {
+        let grid = CharGrid::from("ab\ncd");
+        crate::assert_grid_eq!(grid, grid.clone().toggle_axes());
+    }
+
+    #[test]
+    #[should_panic(expected = "generation 2\ncells differing: 1")]
+    fn assert_grid_eq_fails() {
+        crate::assert_grid_eq!(
+            CharGrid::from("ab"),
+            CharGrid::from("ax"),
+            "generation {}",
+            2
+        );
+    }
+}
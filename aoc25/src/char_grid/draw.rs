@@ -0,0 +1,201 @@
+use std::iter;
+
+use crate::char_grid::{CharGrid, CoordIter, Point};
+
+/// The cells on a straight line from `from` to `to`, both included, using Bresenham's algorithm.
+/// Works for any slope, so horizontal, vertical and diagonal lines are all exact.
+pub fn line_iter(from: Point, to: Point) -> impl CoordIter {
+    iter::from_coroutine(
+        #[coroutine]
+        move || {
+            let (mut x, mut y) = (from.0 as i64, from.1 as i64);
+            let (end_x, end_y) = (to.0 as i64, to.1 as i64);
+            let dx = (end_x - x).abs();
+            let dy = -(end_y - y).abs();
+            let step_x = if x < end_x { 1 } else { -1 };
+            let step_y = if y < end_y { 1 } else { -1 };
+            let mut error = dx + dy;
+
+            loop {
+                yield (x as usize, y as usize);
+
+                if (x, y) == (end_x, end_y) {
+                    break;
+                }
+
+                let doubled = 2 * error;
+                if doubled >= dy {
+                    error += dy;
+                    x += step_x;
+                }
+                if doubled <= dx {
+                    error += dx;
+                    y += step_y;
+                }
+            }
+        },
+    )
+}
+
+/// Every cell in the rectangle with opposite corners `a` and `b`, row by row. The corners can be
+/// given in any order.
+pub fn rect_iter(a: Point, b: Point) -> impl CoordIter {
+    let (min_x, min_y, max_x, max_y) = normalize(a, b);
+    (min_y..=max_y).flat_map(move |y| (min_x..=max_x).map(move |x| (x, y)))
+}
+
+/// The cells on the edge of the rectangle with opposite corners `a` and `b`, each once, row by
+/// row. Only the edges are walked, so this is linear in the perimeter rather than the area.
+pub fn rect_outline_iter(a: Point, b: Point) -> impl CoordIter {
+    let (min_x, min_y, max_x, max_y) = normalize(a, b);
+    let row = move |y| (min_x..=max_x).map(move |x| (x, y));
+    let sides = (min_y + 1..max_y).flat_map(move |y| {
+        // A one cell wide rectangle has a single side
+        let right = (max_x != min_x).then_some((max_x, y));
+        iter::once((min_x, y)).chain(right)
+    });
+    let bottom = (max_y != min_y).then(|| row(max_y)).into_iter().flatten();
+
+    row(min_y).chain(sides).chain(bottom)
+}
+
+/// The cells on the lines joining `points` in order. Each vertex is yielded once.
+pub fn polyline_iter(points: &[Point]) -> impl CoordIter + '_ {
+    segments_iter(points, false)
+}
+
+/// Like `polyline_iter`, with an extra line from the last point back to the first.
+pub fn polygon_iter(points: &[Point]) -> impl CoordIter + '_ {
+    segments_iter(points, true)
+}
+
+fn segments_iter(points: &[Point], closed: bool) -> impl CoordIter + '_ {
+    iter::from_coroutine(
+        #[coroutine]
+        move || {
+            let count = if closed {
+                points.len()
+            } else {
+                points.len().saturating_sub(1)
+            };
+
+            for i in 0..count {
+                let (start, end) = (points[i], points[(i + 1) % points.len()]);
+                // The end is yielded as the start of the next line
+                for point in line_iter(start, end) {
+                    if point != end {
+                        yield point;
+                    }
+                }
+            }
+
+            if (!closed || points.len() == 1)
+                && let Some(&last) = points.last()
+            {
+                yield last;
+            }
+        },
+    )
+}
+
+fn normalize(a: Point, b: Point) -> (usize, usize, usize, usize) {
+    (a.0.min(b.0), a.1.min(b.1), a.0.max(b.0), a.1.max(b.1))
+}
+
+/// Drawing methods set every cell of a shape to a char. Cells outside the grid are skipped, so
+/// shapes may hang over the edge.
+impl CharGrid {
+    pub fn draw_line(&mut self, from: Point, to: Point, value: char) {
+        self.paint(line_iter(from, to), value);
+    }
+
+    pub fn fill_rect(&mut self, a: Point, b: Point, value: char) {
+        self.paint(rect_iter(a, b), value);
+    }
+
+    pub fn draw_rect(&mut self, a: Point, b: Point, value: char) {
+        self.paint(rect_outline_iter(a, b), value);
+    }
+
+    pub fn draw_polyline(&mut self, points: &[Point], value: char) {
+        self.paint(polyline_iter(points), value);
+    }
+
+    pub fn draw_polygon(&mut self, points: &[Point], value: char) {
+        self.paint(polygon_iter(points), value);
+    }
+
+    fn paint(&mut self, points: impl CoordIter, value: char) {
+        for (x, y) in points {
+            if x < self.width() && y < self.height() {
+                self.set(x, y, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_grid_eq;
+
+    #[test]
+    fn lines() {
+        let horizontal: Vec<Point> = line_iter((3, 1), (0, 1)).collect();
+        assert_eq!(horizontal, vec![(3, 1), (2, 1), (1, 1), (0, 1)]);
+
+        let diagonal: Vec<Point> = line_iter((0, 3), (3, 0)).collect();
+        assert_eq!(diagonal, vec![(0, 3), (1, 2), (2, 1), (3, 0)]);
+
+        let steep: Vec<Point> = line_iter((0, 0), (1, 3)).collect();
+        assert_eq!(steep, vec![(0, 0), (0, 1), (1, 2), (1, 3)]);
+
+        assert_eq!(line_iter((2, 2), (2, 2)).collect::<Vec<_>>(), vec![(2, 2)]);
+    }
+
+    #[test]
+    fn rects() {
+        let mut grid = CharGrid::with_default_char(5, 4, '.');
+        grid.draw_rect((3, 3), (0, 0), '#');
+        grid.fill_rect((2, 1), (1, 2), 'o');
+        grid.fill_rect((4, 3), (9, 9), 'x');
+
+        assert_grid_eq!(grid, CharGrid::from("####.\n#oo#.\n#oo#.\n####x"));
+        assert_eq!(rect_outline_iter((0, 0), (3, 3)).count(), 12);
+        assert_eq!(rect_outline_iter((1, 1), (1, 4)).count(), 4);
+        assert_eq!(rect_outline_iter((2, 2), (2, 2)).count(), 1);
+        assert_eq!(
+            rect_outline_iter((0, 0), (2, 2)).collect::<Vec<_>>(),
+            vec![
+                (0, 0),
+                (1, 0),
+                (2, 0),
+                (0, 1),
+                (2, 1),
+                (0, 2),
+                (1, 2),
+                (2, 2)
+            ]
+        );
+        // Huge rectangles are fine, as only the edges are visited
+        assert_eq!(
+            rect_outline_iter((0, 0), (99_999, 99_999)).count(),
+            4 * 99_999
+        );
+    }
+
+    #[test]
+    fn polylines() {
+        let points = [(0, 0), (3, 0), (3, 2), (0, 2)];
+        let open: Vec<Point> = polyline_iter(&points).collect();
+        assert_eq!(open.len(), 9);
+        assert_eq!(open.last(), Some(&(0, 2)));
+        assert_eq!(polygon_iter(&points).count(), 10);
+        assert_eq!(polygon_iter(&[(1, 1)]).collect::<Vec<_>>(), vec![(1, 1)]);
+        assert_eq!(polyline_iter(&[]).count(), 0);
+
+        let mut grid = CharGrid::with_default_char(5, 4, '.');
+        grid.draw_polygon(&[(0, 0), (4, 0), (0, 3)], '#');
+        assert_grid_eq!(grid, CharGrid::from("#####\n#..#.\n###..\n#...."));
+    }
+}
@@ -0,0 +1,230 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::char_grid::CharGrid;
+
+/// A grid without edges, addressed with signed coordinates. Cells that were never set read as
+/// the default char.
+///
+/// Storage grows on `set` in whichever direction is needed, at least doubling along that axis, so
+/// filling a grid one cell at a time takes amortized constant time per cell.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InfiniteGrid {
+    default_char: char,
+    /// Coordinate of the first stored cell
+    origin: (i64, i64),
+    width: usize,
+    height: usize,
+    data: Vec<char>,
+    bounds: Option<(i64, i64, i64, i64)>,
+}
+
+impl InfiniteGrid {
+    const DEFAULT_CHAR: char = '.';
+
+    pub fn new() -> Self {
+        InfiniteGrid::with_default_char(Self::DEFAULT_CHAR)
+    }
+
+    pub fn with_default_char(default_char: char) -> Self {
+        InfiniteGrid {
+            default_char,
+            origin: (0, 0),
+            width: 0,
+            height: 0,
+            data: vec![],
+            bounds: None,
+        }
+    }
+
+    pub fn default_char(&self) -> char {
+        self.default_char
+    }
+
+    pub fn get(&self, x: i64, y: i64) -> char {
+        self.index(x, y)
+            .map_or(self.default_char, |index| self.data[index])
+    }
+
+    pub fn set(&mut self, x: i64, y: i64, value: char) {
+        if self.index(x, y).is_none() {
+            self.grow_to(x, y);
+        }
+
+        let index = self.index(x, y).expect("grid grew to hold the cell");
+        self.data[index] = value;
+        self.bounds = Some(match self.bounds {
+            None => (x, y, x, y),
+            Some((min_x, min_y, max_x, max_y)) => {
+                (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y))
+            }
+        });
+    }
+
+    /// The smallest `(min_x, min_y, max_x, max_y)` box holding every cell that was set, inclusive.
+    pub fn bounds(&self) -> Option<(i64, i64, i64, i64)> {
+        self.bounds
+    }
+
+    /// The width of the bounding box.
+    pub fn width(&self) -> usize {
+        self.bounds
+            .map_or(0, |(min_x, _, max_x, _)| (max_x - min_x + 1) as usize)
+    }
+
+    /// The height of the bounding box.
+    pub fn height(&self) -> usize {
+        self.bounds
+            .map_or(0, |(_, min_y, _, max_y)| (max_y - min_y + 1) as usize)
+    }
+
+    /// Every coordinate in the bounding box, row by row.
+    pub fn coordinates_iter(&self) -> impl Iterator<Item = (i64, i64)> + use<> {
+        let (min_x, min_y, max_x, max_y) = self.bounds.unwrap_or((0, 0, -1, -1));
+        (min_y..=max_y).flat_map(move |y| (min_x..=max_x).map(move |x| (x, y)))
+    }
+
+    /// Copies the bounding box into a `CharGrid`, with its top-left cell at `(0, 0)`.
+    pub fn to_char_grid(&self) -> CharGrid {
+        let mut grid = CharGrid::with_default_char(self.width(), self.height(), self.default_char);
+        if let Some((min_x, min_y, _, _)) = self.bounds {
+            for (x, y) in self.coordinates_iter() {
+                grid.set((x - min_x) as usize, (y - min_y) as usize, self.get(x, y));
+            }
+        }
+        grid
+    }
+
+    fn index(&self, x: i64, y: i64) -> Option<usize> {
+        let dx = x - self.origin.0;
+        let dy = y - self.origin.1;
+        (dx >= 0 && dy >= 0 && (dx as usize) < self.width && (dy as usize) < self.height)
+            .then(|| dy as usize * self.width + dx as usize)
+    }
+
+    /// Reallocates so `(x, y)` is stored, growing each axis that needs it by at least its current
+    /// length.
+    fn grow_to(&mut self, x: i64, y: i64) {
+        let (origin_x, width) = grow_axis(self.origin.0, self.width, x);
+        let (origin_y, height) = grow_axis(self.origin.1, self.height, y);
+        let mut data = vec![self.default_char; width * height];
+
+        let offset_x = (self.origin.0 - origin_x) as usize;
+        let offset_y = (self.origin.1 - origin_y) as usize;
+        for (row, cells) in self.data.chunks(self.width.max(1)).enumerate() {
+            let start = (row + offset_y) * width + offset_x;
+            data[start..start + cells.len()].copy_from_slice(cells);
+        }
+
+        self.origin = (origin_x, origin_y);
+        self.width = width;
+        self.height = height;
+        self.data = data;
+    }
+}
+
+/// The new start and length of an axis so it holds `value`.
+fn grow_axis(start: i64, len: usize, value: i64) -> (i64, usize) {
+    if len == 0 {
+        return (value, 1);
+    }
+
+    let end = start + len as i64;
+    if value < start {
+        let extra = ((start - value) as usize).max(len);
+        (start - extra as i64, len + extra)
+    } else if value >= end {
+        let extra = ((value - end + 1) as usize).max(len);
+        (start, len + extra)
+    } else {
+        (start, len)
+    }
+}
+
+impl Default for InfiniteGrid {
+    fn default() -> Self {
+        InfiniteGrid::new()
+    }
+}
+
+/// Places the grid's `(0, 0)` at `(0, 0)`.
+impl From<&CharGrid> for InfiniteGrid {
+    fn from(grid: &CharGrid) -> Self {
+        let mut infinite = InfiniteGrid::new();
+        for (x, y) in grid.coordinates_iter() {
+            infinite.set(x as i64, y as i64, grid.get(x, y));
+        }
+        infinite
+    }
+}
+
+/// Shows only the bounding box of the cells that were set.
+impl Display for InfiniteGrid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Some((min_x, min_y, max_x, max_y)) = self.bounds else {
+            return Ok(());
+        };
+
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                write!(f, "{}", self.get(x, y))?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grows_in_every_direction() {
+        let mut grid = InfiniteGrid::new();
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.to_string(), "");
+
+        grid.set(0, 0, 'a');
+        grid.set(-3, 1, 'b');
+        grid.set(2, -2, 'c');
+        grid.set(-1, 0, 'd');
+
+        assert_eq!(grid.get(0, 0), 'a');
+        assert_eq!(grid.get(-3, 1), 'b');
+        assert_eq!(grid.get(2, -2), 'c');
+        assert_eq!(grid.get(-1, 0), 'd');
+        assert_eq!(grid.get(100, -100), '.');
+        assert_eq!(grid.bounds(), Some((-3, -2, 2, 1)));
+        assert_eq!((grid.width(), grid.height()), (6, 4));
+        assert_eq!(grid.to_string(), ".....c\n......\n..da..\nb.....\n");
+    }
+
+    #[test]
+    fn growth_is_geometric() {
+        let mut grid = InfiniteGrid::with_default_char(' ');
+        let mut reallocations = 0;
+
+        for x in 0..1000 {
+            let width = grid.width;
+            grid.set(-x, 0, '#');
+            if grid.width != width {
+                reallocations += 1;
+            }
+        }
+
+        assert!(reallocations <= 11);
+        assert_eq!(grid.bounds(), Some((-999, 0, 0, 0)));
+        assert_eq!(grid.coordinates_iter().count(), 1000);
+    }
+
+    #[test]
+    fn char_grid_round_trip() {
+        let grid = CharGrid::from("ab\ncd");
+        let mut infinite = InfiniteGrid::from(&grid);
+        assert_eq!(infinite.to_char_grid(), grid);
+
+        infinite.set(-1, -1, 'x');
+        assert_eq!(infinite.to_char_grid(), CharGrid::from("x..\n.ab\n.cd"));
+    }
+}
@@ -4,7 +4,16 @@ use std::slice::{Iter, IterMut};
 use std::{fmt, iter, mem};
 
 use crate::char_grid::slice::Slice;
-
+use crate::direction::{Direction, Direction8};
+use crate::math::vector::Vec2;
+
+pub mod beam;
+pub mod diff;
+pub mod draw;
+pub mod infinite;
+pub mod paths;
+pub mod raycast;
+pub mod region;
 pub mod slice;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -14,9 +23,35 @@ pub struct CharGrid {
     data: Vec<Vec<char>>,
     axes_enabled: bool,
     cell_width: usize,
+    wrap: Wrap,
+}
+
+pub type Point = (usize, usize);
+
+pub trait CoordIter = Iterator<Item = Point>;
+
+/// Which edges of a grid connect to the opposite edge, as on a torus. Stepping off a wrapped edge
+/// continues from the other side instead of leaving the grid.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Wrap {
+    #[default]
+    None,
+    /// The left and right edges connect
+    Horizontal,
+    /// The top and bottom edges connect
+    Vertical,
+    Both,
 }
 
-pub trait CoordIter = Iterator<Item = (usize, usize)>;
+impl Wrap {
+    pub fn horizontal(self) -> bool {
+        matches!(self, Wrap::Horizontal | Wrap::Both)
+    }
+
+    pub fn vertical(self) -> bool {
+        matches!(self, Wrap::Vertical | Wrap::Both)
+    }
+}
 
 impl CharGrid {
     const DEFAULT_CHAR: char = '?';
@@ -32,6 +67,7 @@ impl CharGrid {
             data: vec![vec![default_char; width]; height],
             axes_enabled: false,
             cell_width: 3,
+            wrap: Wrap::None,
         }
     }
 
@@ -105,6 +141,16 @@ impl CharGrid {
         &mut self.cell_width
     }
 
+    /// Makes stepping, neighbors and raycasts wrap around the given edges.
+    pub fn with_wrap(mut self, wrap: Wrap) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    pub fn wrap(&self) -> Wrap {
+        self.wrap
+    }
+
     pub fn push_x(&mut self, y: usize, value: char) {
         self.resize(self.width + 1, self.height);
         self.set(self.width - 1, y, value);
@@ -115,45 +161,97 @@ impl CharGrid {
         self.set(x, self.height - 1, value);
     }
 
+    /// Appends a row, widening the grid if the row is longer than the current width. Shorter rows
+    /// are padded with the default char.
+    pub fn push_row(&mut self, row: &str) {
+        let mut row: Vec<char> = row.chars().collect();
+
+        if row.len() > self.width {
+            self.resize(row.len(), self.height);
+        }
+
+        row.resize(self.width, Self::DEFAULT_CHAR);
+        self.data.push(row);
+        self.height += 1;
+    }
+
     pub fn coordinates_iter(&self) -> impl CoordIter {
         (0..self.height()).flat_map(move |y| (0..self.width()).map(move |x| (x, y)))
     }
 
+    /// The orthogonal neighbors of `(x, y)`. Each cell is yielded once and never `(x, y)` itself,
+    /// even when a wrapped axis is only one or two cells long.
     pub fn neighbors_iter(&self, x: usize, y: usize) -> impl CoordIter {
-        self.deltas_iter(x, y, &[(0, -1), (1, 0), (0, 1), (-1, 0)])
+        self.distinct_steps((x, y), Direction::iter())
     }
 
+    /// The diagonal neighbors of `(x, y)`, deduplicated like [`CharGrid::neighbors_iter`].
     pub fn diagonals_iter(&self, x: usize, y: usize) -> impl CoordIter {
-        self.deltas_iter(x, y, &[(-1, -1), (1, -1), (1, 1), (-1, 1)])
+        self.distinct_steps((x, y), Direction8::DIAGONALS.into_iter())
+    }
+
+    fn distinct_steps<D: Into<Vec2>>(
+        &self,
+        point: Point,
+        directions: impl Iterator<Item = D>,
+    ) -> impl CoordIter {
+        // At most four directions, and `point` itself is never kept, so it can fill unused slots
+        let mut seen = [point; 4];
+        let mut len = 0;
+        directions
+            .filter_map(move |direction| self.step(point, direction))
+            .filter(move |&next| {
+                if seen.contains(&next) {
+                    return false;
+                }
+                seen[len] = next;
+                len += 1;
+                true
+            })
+    }
+
+    /// The cell one step away in `direction`, or `None` when that leaves the grid.
+    pub fn step(&self, point: Point, direction: impl Into<Vec2>) -> Option<Point> {
+        self.step_by(point, direction.into())
     }
 
-    fn deltas_iter(&self, x: usize, y: usize, deltas: &[(i64, i64)]) -> impl CoordIter {
-        let x = x as i64;
-        let y = y as i64;
-        deltas
-            .iter()
-            .map(move |(dx, dy)| (x + dx, y + dy))
-            .filter(|(nx, ny)| self.contains(*nx, *ny))
-            .map(|(nx, ny)| (nx as usize, ny as usize))
+    /// The cell `n` steps away in `direction`, or `None` when that leaves the grid.
+    pub fn step_n(&self, point: Point, direction: impl Into<Vec2>, n: i64) -> Option<Point> {
+        self.step_by(point, direction.into() * n)
     }
 
+    /// The cell at `point` offset by `delta`, or `None` when that leaves the grid. Wrapped axes
+    /// never leave the grid.
+    pub fn step_by(&self, (x, y): Point, delta: Vec2) -> Option<Point> {
+        let x = wrap_axis(x as i64 + delta.x, self.width, self.wrap.horizontal());
+        let y = wrap_axis(y as i64 + delta.y, self.height, self.wrap.vertical());
+        self.contains(x, y).then_some((x as usize, y as usize))
+    }
+
+    /// Walks from `(x, y)` in steps of `delta` until leaving the grid or reaching a char in
+    /// `stop_at`, which is not yielded. On a wrapping grid the ray also stops before coming back
+    /// to `(x, y)`.
     pub fn raycast_iter(
         &self,
         x: usize,
         y: usize,
-        dx: i64,
-        dy: i64,
+        delta: impl Into<Vec2>,
         stop_at: &[char],
     ) -> impl CoordIter {
+        let delta = delta.into();
         iter::from_coroutine(
             #[coroutine]
             move || {
-                let (mut x, mut y) = (x as i64, y as i64);
-
-                while self.contains(x, y) && !stop_at.contains(&self.get(x as usize, y as usize)) {
-                    yield (x as usize, y as usize);
-                    x += dx;
-                    y += dy;
+                let start = self.step_by((x, y), Vec2::ZERO);
+                let mut point = start;
+
+                while let Some((x, y)) = point
+                    && !stop_at.contains(&self.get(x, y))
+                {
+                    yield (x, y);
+                    point = self
+                        .step_by((x, y), delta)
+                        .filter(|&next| Some(next) != start);
                 }
             },
         )
@@ -285,6 +383,15 @@ impl From<&str> for CharGrid {
     }
 }
 
+/// Brings a coordinate back onto an axis of length `len` when it wraps.
+fn wrap_axis(value: i64, len: usize, wraps: bool) -> i64 {
+    if wraps && len > 0 {
+        value.rem_euclid(len as i64)
+    } else {
+        value
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -371,6 +478,79 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn step() {
+        let grid = CharGrid::new(4, 3);
+        assert_eq!(grid.step((1, 1), Direction::Up), Some((1, 0)));
+        assert_eq!(grid.step((1, 0), Direction::Up), None);
+        assert_eq!(grid.step((3, 2), Direction8::DownRight), None);
+        assert_eq!(grid.step((0, 2), Direction8::UpRight), Some((1, 1)));
+        assert_eq!(grid.step_n((0, 1), Direction::Right, 3), Some((3, 1)));
+        assert_eq!(grid.step_n((0, 1), Direction::Right, 4), None);
+        assert_eq!(grid.step_by((2, 2), Vec2::new(-2, -2)), Some((0, 0)));
+    }
+
+    #[test]
+    fn raycast_iter() {
+        let grid = CharGrid::from("....\n..#.\n....");
+        let down: Vec<Point> = grid.raycast_iter(2, 0, Direction::Down, &['#']).collect();
+        assert_eq!(down, vec![(2, 0)]);
+        let left: Vec<Point> = grid.raycast_iter(2, 2, (-1, 0), &['#']).collect();
+        assert_eq!(left, vec![(2, 2), (1, 2), (0, 2)]);
+    }
+
+    #[test]
+    fn wrapped_neighbors() {
+        let grid = CharGrid::new(4, 3).with_wrap(Wrap::Both);
+        let neighbors: Vec<Point> = grid.neighbors_iter(0, 0).collect();
+        assert_eq!(neighbors, vec![(0, 2), (1, 0), (0, 1), (3, 0)]);
+        let diagonals: Vec<Point> = grid.diagonals_iter(3, 2).collect();
+        assert_eq!(diagonals, vec![(2, 1), (0, 1), (0, 0), (2, 0)]);
+
+        let grid = grid.with_wrap(Wrap::Horizontal);
+        let neighbors: Vec<Point> = grid.neighbors_iter(0, 0).collect();
+        assert_eq!(neighbors, vec![(1, 0), (0, 1), (3, 0)]);
+        assert_eq!(grid.step((3, 1), Direction::Right), Some((0, 1)));
+        assert_eq!(grid.step((3, 2), Direction::Down), None);
+        assert_eq!(grid.step_n((1, 1), Direction::Left, 6), Some((3, 1)));
+
+        let grid = grid.with_wrap(Wrap::Vertical);
+        assert_eq!(grid.step((3, 1), Direction::Right), None);
+        assert_eq!(grid.step((3, 2), Direction::Down), Some((3, 0)));
+    }
+
+    #[test]
+    fn wrapped_neighbors_on_narrow_grids() {
+        // Left and right both lead back to the cell itself
+        let grid = CharGrid::new(1, 3).with_wrap(Wrap::Both);
+        let neighbors: Vec<Point> = grid.neighbors_iter(0, 1).collect();
+        assert_eq!(neighbors, vec![(0, 0), (0, 2)]);
+        let diagonals: Vec<Point> = grid.diagonals_iter(0, 1).collect();
+        assert_eq!(diagonals, vec![(0, 0), (0, 2)]);
+
+        // Left and right both lead to the other column
+        let grid = CharGrid::new(2, 3).with_wrap(Wrap::Both);
+        let neighbors: Vec<Point> = grid.neighbors_iter(0, 1).collect();
+        assert_eq!(neighbors, vec![(0, 0), (1, 1), (0, 2)]);
+        let diagonals: Vec<Point> = grid.diagonals_iter(0, 1).collect();
+        assert_eq!(diagonals, vec![(1, 0), (1, 2)]);
+    }
+
+    #[test]
+    fn wrapped_raycast() {
+        let grid = CharGrid::from("....\n.#..").with_wrap(Wrap::Horizontal);
+        let right: Vec<Point> = grid.raycast_iter(2, 1, Direction::Right, &['#']).collect();
+        assert_eq!(right, vec![(2, 1), (3, 1), (0, 1)]);
+
+        // Without anything to hit, the ray goes around once
+        let left: Vec<Point> = grid.raycast_iter(1, 0, Direction::Left, &['#']).collect();
+        assert_eq!(left, vec![(1, 0), (0, 0), (3, 0), (2, 0)]);
+
+        let grid = grid.with_wrap(Wrap::Both);
+        let diagonal: Vec<Point> = grid.raycast_iter(0, 0, (1, 1), &[]).collect();
+        assert_eq!(diagonal, vec![(0, 0), (1, 1), (2, 0), (3, 1)]);
+    }
+
     #[test]
     fn from_str() {
         let str = "AAA
@@ -456,6 +636,19 @@ AAA";
         assert_eq!(grid.get(1, 5), '?');
     }
 
+    #[test]
+    fn push_row() {
+        let mut grid = CharGrid::default();
+
+        grid.push_row("AB");
+        grid.push_row("CDE");
+        grid.push_row("F");
+
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid.to_raw(), "AB?\nCDE\nF??");
+    }
+
     #[test]
     fn columns_iter() {
         let grid: CharGrid = "ABC
@@ -0,0 +1,270 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::{
+    char_grid::{CharGrid, Point},
+    math::exact::Exact,
+};
+
+/// How `CharGrid::count_paths` walks the graph. Both give the same counts.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Strategy {
+    /// Depth-first from the source, memoizing each cell once its successors are counted
+    #[default]
+    TopDown,
+    /// Finds every reachable cell first, then counts them in reverse topological order
+    BottomUp,
+}
+
+/// The number of distinct paths from each reachable cell to a sink.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathCounts {
+    source: Point,
+    counts: HashMap<Point, Exact>,
+}
+
+impl PathCounts {
+    pub fn source(&self) -> Point {
+        self.source
+    }
+
+    /// The number of paths from the source to any sink.
+    pub fn total(&self) -> &Exact {
+        &self.counts[&self.source]
+    }
+
+    /// The number of paths from a cell to any sink, if the cell is reachable from the source.
+    pub fn get(&self, point: Point) -> Option<&Exact> {
+        self.counts.get(&point)
+    }
+
+    /// Every reachable cell with its count, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &Exact)> {
+        self.counts.iter().map(|(&point, count)| (point, count))
+    }
+
+    /// The number of reachable cells.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+}
+
+enum Frame {
+    Visit(Point),
+    Finish(Point, Vec<Point>),
+}
+
+impl CharGrid {
+    /// Counts the distinct paths from `source` through the directed graph where `next` gives the
+    /// cells each cell leads to. A cell with no successors is a sink and ends one path. Counts
+    /// never overflow, and no recursion is used, so long paths are fine.
+    ///
+    /// Panics if the transitions form a cycle reachable from the source.
+    pub fn count_paths(
+        &self,
+        source: Point,
+        strategy: Strategy,
+        next: impl Fn(Point, char) -> Vec<Point>,
+    ) -> PathCounts {
+        let next = |point: Point| next(point, self.get(point.0, point.1));
+        let counts = match strategy {
+            Strategy::TopDown => count_top_down(source, next),
+            Strategy::BottomUp => count_bottom_up(source, next),
+        };
+        PathCounts { source, counts }
+    }
+}
+
+fn count_top_down(source: Point, next: impl Fn(Point) -> Vec<Point>) -> HashMap<Point, Exact> {
+    let mut counts: HashMap<Point, Exact> = HashMap::new();
+    let mut in_progress = HashSet::new();
+    let mut stack = vec![Frame::Visit(source)];
+
+    while let Some(frame) = stack.pop() {
+        match frame {
+            Frame::Visit(point) => {
+                if counts.contains_key(&point) {
+                    continue;
+                }
+                // Every unfinished visit of a cell sits above its `Finish`, so meeting the cell
+                // again before finishing it means it leads back to itself
+                if !in_progress.insert(point) {
+                    panic!("transitions form a cycle through {:?}", point);
+                }
+
+                let successors = next(point);
+                let unvisited: Vec<Point> = successors
+                    .iter()
+                    .filter(|successor| !counts.contains_key(successor))
+                    .copied()
+                    .collect();
+                stack.push(Frame::Finish(point, successors));
+                stack.extend(unvisited.into_iter().map(Frame::Visit));
+            }
+            Frame::Finish(point, successors) => {
+                let count = sum_successors(&counts, &successors);
+                in_progress.remove(&point);
+                counts.insert(point, count);
+            }
+        }
+    }
+
+    counts
+}
+
+fn count_bottom_up(source: Point, next: impl Fn(Point) -> Vec<Point>) -> HashMap<Point, Exact> {
+    // Find every reachable cell and its successors
+    let mut graph: HashMap<Point, Vec<Point>> = HashMap::new();
+    let mut stack = vec![source];
+    while let Some(point) = stack.pop() {
+        if graph.contains_key(&point) {
+            continue;
+        }
+        let successors = next(point);
+        stack.extend(
+            successors
+                .iter()
+                .filter(|successor| !graph.contains_key(successor)),
+        );
+        graph.insert(point, successors);
+    }
+
+    // Order the cells so each comes before everything it leads to
+    let mut in_degrees: HashMap<Point, usize> = graph.keys().map(|&point| (point, 0)).collect();
+    for successor in graph.values().flatten() {
+        *in_degrees
+            .get_mut(successor)
+            .expect("successor is reachable") += 1;
+    }
+    let mut queue: VecDeque<Point> = in_degrees
+        .iter()
+        .filter(|(_, in_degree)| **in_degree == 0)
+        .map(|(&point, _)| point)
+        .collect();
+    let mut order = Vec::with_capacity(graph.len());
+    while let Some(point) = queue.pop_front() {
+        order.push(point);
+        for successor in &graph[&point] {
+            let in_degree = in_degrees
+                .get_mut(successor)
+                .expect("successor is reachable");
+            *in_degree -= 1;
+            if *in_degree == 0 {
+                queue.push_back(*successor);
+            }
+        }
+    }
+    if order.len() < graph.len() {
+        let point = in_degrees
+            .iter()
+            .find(|(_, in_degree)| **in_degree > 0)
+            .map(|(&point, _)| point)
+            .expect("a cell is left in the cycle");
+        panic!("transitions form a cycle through {:?}", point);
+    }
+
+    let mut counts = HashMap::with_capacity(order.len());
+    for point in order.into_iter().rev() {
+        let count = sum_successors(&counts, &graph[&point]);
+        counts.insert(point, count);
+    }
+
+    counts
+}
+
+/// Successors are counted once per edge, so two transitions to the same cell count twice.
+fn sum_successors(counts: &HashMap<Point, Exact>, successors: &[Point]) -> Exact {
+    if successors.is_empty() {
+        return Exact::from(1);
+    }
+
+    let mut total = Exact::default();
+    for successor in successors {
+        total += &counts[successor];
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direction::Direction;
+
+    const STRATEGIES: [Strategy; 2] = [Strategy::TopDown, Strategy::BottomUp];
+
+    /// Every cell leads right and down, so a cell has a binomial number of paths to the corner.
+    fn lattice(grid: &CharGrid) -> impl Fn(Point, char) -> Vec<Point> + '_ {
+        |point, _| {
+            [Direction::Right, Direction::Down]
+                .into_iter()
+                .filter_map(|direction| grid.step(point, direction))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn lattice_paths() {
+        let grid = CharGrid::new(3, 3);
+
+        for strategy in STRATEGIES {
+            let counts = grid.count_paths((0, 0), strategy, lattice(&grid));
+            assert_eq!(counts.total(), &Exact::from(6));
+            assert_eq!(counts.get((1, 1)), Some(&Exact::from(2)));
+            assert_eq!(counts.get((2, 2)), Some(&Exact::from(1)));
+            assert_eq!(counts.len(), 9);
+        }
+    }
+
+    #[test]
+    fn splitters() {
+        let grid = CharGrid::from(
+            ".......S.......\n...............\n.......^.......\n...............\n\
+             ......^.^......\n...............\n.....^.^.^.....\n...............\n\
+             ....^.^...^....\n...............\n...^.^...^.^...\n...............\n\
+             ..^...^.....^..\n...............\n.^.^.^.^.^...^.\n...............",
+        );
+        let next = |point, ch| match ch {
+            '^' => [Direction::Left, Direction::Right]
+                .into_iter()
+                .filter_map(|direction| grid.step(point, direction))
+                .collect(),
+            _ => grid.step(point, Direction::Down).into_iter().collect(),
+        };
+
+        for strategy in STRATEGIES {
+            let counts = grid.count_paths((7, 0), strategy, next);
+            assert_eq!(counts.total(), &Exact::from(40));
+        }
+    }
+
+    #[test]
+    fn counts_do_not_overflow() {
+        let grid = CharGrid::new(70, 70);
+
+        let top_down = grid.count_paths((0, 0), Strategy::TopDown, lattice(&grid));
+        let bottom_up = grid.count_paths((0, 0), Strategy::BottomUp, lattice(&grid));
+        assert!(top_down.total().is_big());
+        assert_eq!(top_down, bottom_up);
+        assert_eq!(
+            top_down.total().to_string(),
+            "23623985175715118288974865541854103729000"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "cycle")]
+    fn top_down_cycle() {
+        let grid = CharGrid::new(2, 1);
+        grid.count_paths((0, 0), Strategy::TopDown, |(x, y), _| vec![(1 - x, y)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "cycle")]
+    fn bottom_up_cycle() {
+        let grid = CharGrid::new(2, 1);
+        grid.count_paths((0, 0), Strategy::BottomUp, |(x, y), _| vec![(1 - x, y)]);
+    }
+}
@@ -0,0 +1,212 @@
+use crate::{
+    char_grid::{CharGrid, Point},
+    math::vector::Vec2,
+};
+
+/// Why a ray stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RayOutcome {
+    /// Reached a cell holding one of the stop chars, which is not part of the path
+    Hit { pos: Point, char: char },
+    /// Left the grid
+    OutOfBounds,
+    /// Travelled the maximum distance without hitting anything
+    MaxDistance,
+    /// Came back around to its start on a wrapping grid
+    Looped,
+}
+
+/// The cells a ray passed through, in order, and what stopped it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Ray {
+    pub path: Vec<Point>,
+    pub outcome: RayOutcome,
+}
+
+impl Ray {
+    pub fn len(&self) -> usize {
+        self.path.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.path.is_empty()
+    }
+
+    /// The last cell the ray passed through.
+    pub fn last(&self) -> Option<Point> {
+        self.path.last().copied()
+    }
+
+    /// The position and char of the cell that stopped the ray, if it hit one.
+    pub fn hit(&self) -> Option<(Point, char)> {
+        match self.outcome {
+            RayOutcome::Hit { pos, char } => Some((pos, char)),
+            _ => None,
+        }
+    }
+}
+
+impl CharGrid {
+    /// Like `raycast_iter`, collecting the path and reporting what stopped the ray.
+    pub fn raycast(&self, from: Point, delta: impl Into<Vec2>, stop_at: &[char]) -> Ray {
+        self.cast(from, delta.into(), stop_at, usize::MAX)
+    }
+
+    /// Like `raycast`, with a path of at most `max_distance` cells.
+    pub fn raycast_max(
+        &self,
+        from: Point,
+        delta: impl Into<Vec2>,
+        stop_at: &[char],
+        max_distance: usize,
+    ) -> Ray {
+        self.cast(from, delta.into(), stop_at, max_distance)
+    }
+
+    /// Casts a ray in each direction, starting next to `from` rather than on it, so `from` may
+    /// hold a stop char itself. Rays are returned in the order of `directions`.
+    pub fn first_hits<D: Into<Vec2>>(
+        &self,
+        from: Point,
+        directions: impl IntoIterator<Item = D>,
+        stop_at: &[char],
+    ) -> Vec<Ray> {
+        directions
+            .into_iter()
+            .map(|direction| {
+                let delta = direction.into();
+                match self.step_by(from, delta) {
+                    Some(start) if start != from => self.cast(start, delta, stop_at, usize::MAX),
+                    Some(_) => Ray {
+                        path: vec![],
+                        outcome: RayOutcome::Looped,
+                    },
+                    None => Ray {
+                        path: vec![],
+                        outcome: RayOutcome::OutOfBounds,
+                    },
+                }
+            })
+            .collect()
+    }
+
+    fn cast(&self, from: Point, delta: Vec2, stop_at: &[char], max_distance: usize) -> Ray {
+        let mut path = vec![];
+        let start = self.step_by(from, Vec2::ZERO);
+        let mut point = start;
+
+        let outcome = loop {
+            let Some((x, y)) = point else {
+                break RayOutcome::OutOfBounds;
+            };
+
+            let ch = self.get(x, y);
+            if stop_at.contains(&ch) {
+                break RayOutcome::Hit {
+                    pos: (x, y),
+                    char: ch,
+                };
+            }
+
+            if path.len() == max_distance {
+                break RayOutcome::MaxDistance;
+            }
+
+            path.push((x, y));
+            point = self.step_by((x, y), delta);
+
+            if point.is_some() && point == start {
+                break RayOutcome::Looped;
+            }
+        };
+
+        Ray { path, outcome }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        char_grid::Wrap,
+        direction::{Direction, Direction8},
+    };
+
+    const GRID: &str = "\
+..#..
+.....
+#.@.#
+.....
+..#..";
+
+    #[test]
+    fn outcomes() {
+        let grid = CharGrid::from(GRID);
+
+        let ray = grid.raycast((2, 1), Direction::Up, &['#']);
+        assert_eq!(ray.path, vec![(2, 1)]);
+        assert_eq!(
+            ray.outcome,
+            RayOutcome::Hit {
+                pos: (2, 0),
+                char: '#'
+            }
+        );
+
+        let ray = grid.raycast((1, 2), Direction::Up, &['#']);
+        assert_eq!(ray.len(), 3);
+        assert_eq!(ray.outcome, RayOutcome::OutOfBounds);
+        assert_eq!(ray.hit(), None);
+
+        // Starting on a stop char hits it straight away
+        let ray = grid.raycast((0, 2), Direction::Right, &['#']);
+        assert!(ray.is_empty());
+        assert_eq!(ray.hit(), Some(((0, 2), '#')));
+    }
+
+    #[test]
+    fn max_distance() {
+        let grid = CharGrid::from(GRID);
+
+        let ray = grid.raycast_max((0, 0), Direction8::DownRight, &['#'], 2);
+        assert_eq!(ray.path, vec![(0, 0), (1, 1)]);
+        assert_eq!(ray.outcome, RayOutcome::MaxDistance);
+
+        let ray = grid.raycast_max((0, 0), Direction8::DownRight, &['@'], 3);
+        assert_eq!(ray.hit(), Some(((2, 2), '@')));
+    }
+
+    #[test]
+    fn first_hit_in_each_direction() {
+        let grid = CharGrid::from(GRID);
+        let rays = grid.first_hits((2, 2), Direction::iter(), &['#', '@']);
+
+        let hits: Vec<_> = rays.iter().map(|ray| ray.hit()).collect();
+        assert_eq!(
+            hits,
+            vec![
+                Some(((2, 0), '#')),
+                Some(((4, 2), '#')),
+                Some(((2, 4), '#')),
+                Some(((0, 2), '#'))
+            ]
+        );
+        assert!(rays.iter().all(|ray| ray.len() == 1));
+
+        let rays = grid.first_hits((0, 0), Direction8::DIAGONALS, &['@']);
+        assert_eq!(rays[0].outcome, RayOutcome::OutOfBounds);
+        assert_eq!(rays[2].last(), Some((1, 1)));
+    }
+
+    #[test]
+    fn wrapped_rays_loop() {
+        let grid = CharGrid::from("...\n.#.").with_wrap(Wrap::Horizontal);
+
+        let ray = grid.raycast((1, 0), Direction::Right, &['#']);
+        assert_eq!(ray.path, vec![(1, 0), (2, 0), (0, 0)]);
+        assert_eq!(ray.outcome, RayOutcome::Looped);
+
+        let ray = grid.raycast((2, 1), Direction::Right, &['#']);
+        assert_eq!(ray.hit(), Some(((1, 1), '#')));
+    }
+}
@@ -0,0 +1,301 @@
+use crate::{
+    bitset::BitSet,
+    char_grid::{CharGrid, Point},
+    direction::Direction8,
+};
+
+const ORTHOGONAL: [Direction8; 4] = [
+    Direction8::Up,
+    Direction8::Right,
+    Direction8::Down,
+    Direction8::Left,
+];
+
+/// Which cells count as touching when filling or labelling.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Connectivity {
+    /// Cells sharing an edge
+    #[default]
+    Four,
+    /// Cells sharing an edge or a corner
+    Eight,
+}
+
+impl Connectivity {
+    pub fn directions(self) -> &'static [Direction8] {
+        match self {
+            Connectivity::Four => &ORTHOGONAL,
+            Connectivity::Eight => &Direction8::ALL,
+        }
+    }
+}
+
+/// A connected group of cells found by `CharGrid::label_regions`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Region {
+    pub id: usize,
+    /// The first cell of the region in row-major order
+    pub start: Point,
+    pub area: usize,
+    /// Cell edges between the region and anything outside it, including the grid edge
+    pub perimeter: usize,
+    /// The smallest `(min_x, min_y, max_x, max_y)` box holding the region, inclusive
+    pub bounds: (usize, usize, usize, usize),
+}
+
+/// Every region of a grid, with the region id of each cell.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Regions {
+    width: usize,
+    height: usize,
+    labels: Vec<Option<usize>>,
+    regions: Vec<Region>,
+}
+
+impl Regions {
+    /// The id of the region holding a cell, or `None` when the cell is in no region.
+    pub fn label(&self, x: usize, y: usize) -> Option<usize> {
+        self.labels[y * self.width + x]
+    }
+
+    pub fn get(&self, id: usize) -> &Region {
+        &self.regions[id]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Region> {
+        self.regions.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    /// Whether a region reaches the outermost rows or columns of the grid.
+    pub fn touches_edge(&self, id: usize) -> bool {
+        let (min_x, min_y, max_x, max_y) = self.regions[id].bounds;
+        min_x == 0 || min_y == 0 || max_x + 1 == self.width || max_y + 1 == self.height
+    }
+}
+
+impl CharGrid {
+    /// The cells reachable from `seed` through cells matching `predicate`, in the order they are
+    /// reached. Empty when the seed itself does not match. Uses an explicit stack, so large areas
+    /// cannot overflow, and follows the grid's wrapping.
+    pub fn flood_fill(
+        &self,
+        seed: Point,
+        connectivity: Connectivity,
+        predicate: impl Fn(char) -> bool,
+    ) -> Vec<Point> {
+        let mut visited = BitSet::new(self.size());
+        let mut stack = vec![];
+        let mut reached = vec![];
+
+        if predicate(self.get(seed.0, seed.1)) {
+            visited.set(self.cell_index(seed), true);
+            stack.push(seed);
+        }
+
+        while let Some(point) = stack.pop() {
+            reached.push(point);
+
+            for &direction in connectivity.directions() {
+                let Some(next) = self.step(point, direction) else {
+                    continue;
+                };
+                let index = self.cell_index(next);
+                if !visited.get(index) && predicate(self.get(next.0, next.1)) {
+                    visited.set(index, true);
+                    stack.push(next);
+                }
+            }
+        }
+
+        reached
+    }
+
+    /// Sets every cell `flood_fill` reaches to `value`, returning how many cells were set.
+    pub fn fill(
+        &mut self,
+        seed: Point,
+        connectivity: Connectivity,
+        predicate: impl Fn(char) -> bool,
+        value: char,
+    ) -> usize {
+        let cells = self.flood_fill(seed, connectivity, predicate);
+        for &(x, y) in &cells {
+            self.set(x, y, value);
+        }
+        cells.len()
+    }
+
+    /// Groups the cells matching `predicate` into connected regions. Ids are given in row-major
+    /// order of each region's first cell.
+    pub fn label_regions(
+        &self,
+        connectivity: Connectivity,
+        predicate: impl Fn(char) -> bool,
+    ) -> Regions {
+        let mut labels = vec![None; self.size()];
+        let mut regions = vec![];
+        let mut stack = vec![];
+
+        for start in self.coordinates_iter() {
+            if labels[self.cell_index(start)].is_some() || !predicate(self.get(start.0, start.1)) {
+                continue;
+            }
+
+            let id = regions.len();
+            let mut region = Region {
+                id,
+                start,
+                area: 0,
+                perimeter: 0,
+                bounds: (start.0, start.1, start.0, start.1),
+            };
+            labels[self.cell_index(start)] = Some(id);
+            stack.push(start);
+
+            while let Some(point @ (x, y)) = stack.pop() {
+                let (min_x, min_y, max_x, max_y) = region.bounds;
+                region.bounds = (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y));
+                region.area += 1;
+                region.perimeter += ORTHOGONAL
+                    .iter()
+                    .filter(|&&direction| {
+                        self.step(point, direction)
+                            .is_none_or(|(nx, ny)| !predicate(self.get(nx, ny)))
+                    })
+                    .count();
+
+                for &direction in connectivity.directions() {
+                    let Some(next) = self.step(point, direction) else {
+                        continue;
+                    };
+                    let index = self.cell_index(next);
+                    if labels[index].is_none() && predicate(self.get(next.0, next.1)) {
+                        labels[index] = Some(id);
+                        stack.push(next);
+                    }
+                }
+            }
+
+            regions.push(region);
+        }
+
+        Regions {
+            width: self.width(),
+            height: self.height(),
+            labels,
+            regions,
+        }
+    }
+
+    fn cell_index(&self, (x, y): Point) -> usize {
+        y * self.width() + x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_grid_eq, char_grid::Wrap};
+
+    const GRID: &str = "\
+##..#
+#..#.
+..#..
+#...#";
+
+    #[test]
+    fn flood_fill_connectivity() {
+        let grid = CharGrid::from(GRID);
+        let is_wall = |ch| ch == '#';
+
+        let four = grid.flood_fill((0, 0), Connectivity::Four, is_wall);
+        assert_eq!(four.len(), 3);
+        let eight = grid.flood_fill((0, 0), Connectivity::Eight, is_wall);
+        assert_eq!(eight.len(), 3);
+        let four = grid.flood_fill((4, 0), Connectivity::Four, is_wall);
+        assert_eq!(four, vec![(4, 0)]);
+        let eight = grid.flood_fill((4, 0), Connectivity::Eight, is_wall);
+        assert_eq!(eight.len(), 3);
+        assert!(
+            grid.flood_fill((2, 0), Connectivity::Four, is_wall)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn fill_area() {
+        let mut grid = CharGrid::from(GRID);
+
+        assert_eq!(
+            grid.fill((2, 0), Connectivity::Four, |ch| ch == '.', 'o'),
+            12
+        );
+        assert_grid_eq!(grid, CharGrid::from("##oo#\n#oo#o\noo#oo\n#ooo#"));
+
+        // Filling with a matching char stops instead of looping
+        assert_eq!(
+            grid.fill((4, 1), Connectivity::Four, |ch| ch == 'o', 'o'),
+            12
+        );
+        assert_eq!(
+            grid.fill((4, 1), Connectivity::Four, |ch| ch == '.', 'o'),
+            0
+        );
+    }
+
+    #[test]
+    fn flood_fill_wraps() {
+        let grid = CharGrid::from(".#.\n.#.").with_wrap(Wrap::Horizontal);
+        let reached = grid.flood_fill((0, 0), Connectivity::Four, |ch| ch == '.');
+        assert_eq!(reached.len(), 4);
+    }
+
+    #[test]
+    fn large_fill() {
+        let mut grid = CharGrid::with_default_char(1000, 1000, '.');
+        let filled = grid.fill((500, 500), Connectivity::Four, |ch| ch == '.', '~');
+        assert_eq!(filled, 1_000_000);
+    }
+
+    #[test]
+    fn labelled_regions() {
+        let grid = CharGrid::from(GRID);
+        let regions = grid.label_regions(Connectivity::Four, |ch| ch == '#');
+
+        assert_eq!(regions.len(), 6);
+        assert_eq!(
+            *regions.get(0),
+            Region {
+                id: 0,
+                start: (0, 0),
+                area: 3,
+                perimeter: 8,
+                bounds: (0, 0, 1, 1)
+            }
+        );
+        assert_eq!(regions.label(1, 0), Some(0));
+        assert_eq!(regions.label(2, 0), None);
+        assert_eq!(regions.label(4, 3), Some(5));
+        assert!(regions.touches_edge(0));
+        assert!(!regions.touches_edge(2));
+
+        let regions = grid.label_regions(Connectivity::Eight, |ch| ch == '#');
+        assert_eq!(regions.len(), 4);
+        assert_eq!(regions.get(1).area, 3);
+        assert_eq!(regions.get(1).perimeter, 12);
+        assert_eq!(regions.get(1).bounds, (2, 0, 4, 2));
+
+        let inner = CharGrid::from("#####\n#...#\n#####");
+        let regions = inner.label_regions(Connectivity::Four, |ch| ch == '.');
+        assert_eq!(regions.get(0).perimeter, 8);
+        assert!(!regions.touches_edge(0));
+    }
+}
@@ -22,6 +22,55 @@ impl<'a> Slice<'a> {
             height,
         }
     }
+
+    /// The column of the parent grid this slice starts at.
+    pub fn x(&self) -> usize {
+        self.x
+    }
+
+    /// The row of the parent grid this slice starts at.
+    pub fn y(&self) -> usize {
+        self.y
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Gets a char relative to the top-left corner of the slice.
+    pub fn get(&self, x: usize, y: usize) -> char {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is outside the slice",
+            x,
+            y
+        );
+        self.grid.get(self.x + x, self.y + y)
+    }
+
+    pub fn row(&self, y: usize) -> &[char] {
+        &self.grid.get_row(self.y + y)[self.x..self.x + self.width]
+    }
+
+    pub fn rows_iter(&self) -> impl Iterator<Item = &[char]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn columns_iter(&self) -> impl Iterator<Item = Vec<char>> {
+        (0..self.width).map(|x| (0..self.height).map(|y| self.get(x, y)).collect())
+    }
+
+    pub fn to_grid(&self) -> CharGrid {
+        let mut grid = CharGrid::new(self.width, self.height);
+        for y in 0..self.height {
+            grid.data[y].copy_from_slice(self.row(y));
+        }
+        grid
+    }
 }
 
 impl Display for Slice<'_> {
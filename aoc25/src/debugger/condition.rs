@@ -0,0 +1,185 @@
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+};
+
+use crate::debugger::Frame;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Comparison {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Comparison::Eq => "==",
+            Comparison::Ne => "!=",
+            Comparison::Lt => "<",
+            Comparison::Le => "<=",
+            Comparison::Gt => ">",
+            Comparison::Ge => ">=",
+        }
+    }
+
+    fn test(self, ordering: Ordering) -> bool {
+        match self {
+            Comparison::Eq => ordering.is_eq(),
+            Comparison::Ne => ordering.is_ne(),
+            Comparison::Lt => ordering.is_lt(),
+            Comparison::Le => ordering.is_le(),
+            Comparison::Gt => ordering.is_gt(),
+            Comparison::Ge => ordering.is_ge(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConditionError {
+    MissingComparison,
+    MissingName,
+    MissingValue,
+}
+
+impl Display for ConditionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ConditionError::MissingComparison => {
+                write!(f, "expected one of == != < <= > >=")
+            }
+            ConditionError::MissingName => write!(f, "missing variable name"),
+            ConditionError::MissingValue => write!(f, "missing value"),
+        }
+    }
+}
+
+impl std::error::Error for ConditionError {}
+
+/// A breakpoint comparing one of a frame's variables with a value, such as `area > 30` or
+/// `valid == true`. Values compare as numbers when both sides parse as one, and as text otherwise.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Condition {
+    pub name: String,
+    pub comparison: Comparison,
+    pub value: String,
+}
+
+impl Condition {
+    /// Parses `name op value`, where a single `=` means `==`.
+    pub fn parse(input: &str) -> Result<Self, ConditionError> {
+        let start = input
+            .find(['=', '!', '<', '>'])
+            .ok_or(ConditionError::MissingComparison)?;
+        let rest = &input[start..];
+
+        let (comparison, len) = if rest.starts_with("==") {
+            (Comparison::Eq, 2)
+        } else if rest.starts_with("!=") {
+            (Comparison::Ne, 2)
+        } else if rest.starts_with("<=") {
+            (Comparison::Le, 2)
+        } else if rest.starts_with(">=") {
+            (Comparison::Ge, 2)
+        } else if rest.starts_with('=') {
+            (Comparison::Eq, 1)
+        } else if rest.starts_with('<') {
+            (Comparison::Lt, 1)
+        } else if rest.starts_with('>') {
+            (Comparison::Gt, 1)
+        } else {
+            return Err(ConditionError::MissingComparison);
+        };
+
+        let name = input[..start].trim();
+        let value = rest[len..].trim();
+        if name.is_empty() {
+            return Err(ConditionError::MissingName);
+        }
+        if value.is_empty() {
+            return Err(ConditionError::MissingValue);
+        }
+
+        Ok(Condition {
+            name: name.to_string(),
+            comparison,
+            value: value.to_string(),
+        })
+    }
+
+    /// Whether the frame has the variable and it compares as asked. Names ignore case.
+    pub fn matches(&self, frame: &Frame) -> bool {
+        let Some(actual) = frame.get(&self.name) else {
+            return false;
+        };
+
+        let ordering = match (actual.parse::<f64>(), self.value.parse::<f64>()) {
+            (Ok(actual), Ok(expected)) => actual.partial_cmp(&expected),
+            _ => Some(actual.as_str().cmp(self.value.as_str())),
+        };
+
+        ordering.is_some_and(|ordering| self.comparison.test(ordering))
+    }
+}
+
+impl Display for Condition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.name,
+            self.comparison.symbol(),
+            self.value
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{char_grid::CharGrid, debugger::Vars};
+
+    #[test]
+    fn parse() {
+        let condition = Condition::parse(" area >= 30 ").unwrap();
+        assert_eq!(condition.name, "area");
+        assert_eq!(condition.comparison, Comparison::Ge);
+        assert_eq!(condition.value, "30");
+        assert_eq!(condition.to_string(), "area >= 30");
+
+        assert_eq!(
+            Condition::parse("valid=true").unwrap().comparison,
+            Comparison::Eq
+        );
+        assert_eq!(
+            Condition::parse("area"),
+            Err(ConditionError::MissingComparison)
+        );
+        assert_eq!(Condition::parse("< 3"), Err(ConditionError::MissingName));
+        assert_eq!(
+            Condition::parse("area !="),
+            Err(ConditionError::MissingValue)
+        );
+    }
+
+    #[test]
+    fn matches() {
+        let frame = Frame {
+            step: 12,
+            grid: CharGrid::default(),
+            vars: Vars::new().with("Area", 9).with("Valid", true),
+        };
+        let matches = |input| Condition::parse(input).unwrap().matches(&frame);
+
+        // Compared as numbers, so 9 is less than 10 even though "9" sorts after "10"
+        assert!(matches("area < 10"));
+        assert!(!matches("area > 10"));
+        assert!(matches("valid == true"));
+        assert!(matches("step >= 12"));
+        assert!(!matches("missing != 0"));
+    }
+}
@@ -0,0 +1,672 @@
+use std::{
+    collections::VecDeque,
+    fmt::{Display, Write as _},
+    io::{self, Write},
+    process,
+    time::Duration,
+};
+
+use crate::{
+    char_grid::CharGrid,
+    debugger::{
+        condition::Condition,
+        terminal::{Key, Terminal},
+        viewport::Viewport,
+    },
+};
+
+pub mod condition;
+pub mod terminal;
+pub mod viewport;
+
+const DEFAULT_HISTORY: usize = 1000;
+const DEFAULT_DELAY: Duration = Duration::from_millis(50);
+const PANEL_WIDTH: usize = 36;
+const SEPARATOR: &str = " │ ";
+const HELP: &str = "n step  p back  space run/pause  g go to  b break  c clear  \
+                    arrows/hjkl scroll  +/- speed  d detach";
+/// The exit code for quitting before the solver is done
+const QUIT_CODE: i32 = 1;
+/// The exit code for Ctrl-C before the solver is done, as the shell reports SIGINT
+const INTERRUPT_CODE: i32 = 130;
+
+/// Named values shown next to the grid, in the order they were added.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Vars {
+    entries: Vec<(String, String)>,
+}
+
+impl Vars {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, name: impl Into<String>, value: impl Display) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    /// Adds a variable, or replaces the value of one with the same name.
+    pub fn set(&mut self, name: impl Into<String>, value: impl Display) {
+        let name = name.into();
+        let value = value.to_string();
+        match self
+            .entries
+            .iter_mut()
+            .find(|(existing, _)| *existing == name)
+        {
+            Some((_, existing)) => *existing = value,
+            None => self.entries.push((name, value)),
+        }
+    }
+
+    /// The value of a variable, ignoring case in the name.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(existing, _)| existing.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+}
+
+/// A snapshot of the solver at one step.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Frame {
+    /// Counts from 1
+    pub step: usize,
+    pub grid: CharGrid,
+    pub vars: Vars,
+}
+
+impl Frame {
+    /// The value of a variable, where `step` is the frame's step unless a variable shadows it.
+    pub fn get(&self, name: &str) -> Option<String> {
+        match self.vars.get(name) {
+            Some(value) => Some(value.to_string()),
+            None if name.eq_ignore_ascii_case("step") => Some(self.step.to_string()),
+            None => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Mode {
+    Paused,
+    Running,
+    /// Running until the given step
+    RunTo(usize),
+    /// No longer stopping or drawing
+    Detached,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum PromptKind {
+    Step,
+    Breakpoint,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Prompt {
+    kind: PromptKind,
+    input: String,
+}
+
+/// Whether a key lets the solver carry on to its next frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Flow {
+    Stay,
+    Resume,
+}
+
+/// An interactive terminal stepper for solvers that animate a `CharGrid`.
+///
+/// The solver hands over a frame at each step with `frame`, which shows the grid in a scrollable
+/// viewport beside a panel of the frame's variables. The debugger starts paused, so each call
+/// waits for a key until the user runs it. Recent frames are kept so the user can step back
+/// through them, and running stops at a chosen step or when a breakpoint condition such as
+/// `area > 30` holds.
+///
+/// Without a terminal, such as when output is piped, frames are skipped and the solver runs
+/// straight through.
+pub struct Debugger {
+    terminal: Option<Terminal>,
+    history: VecDeque<Frame>,
+    history_len: usize,
+    steps: usize,
+    /// The index in `history` of the frame on screen when looking back, `None` for the latest
+    cursor: Option<usize>,
+    mode: Mode,
+    delay: Duration,
+    breakpoints: Vec<Condition>,
+    viewport: Viewport,
+    prompt: Option<Prompt>,
+    message: String,
+    /// Set by `finish`, after which quitting only closes the debugger
+    finished: bool,
+}
+
+impl Debugger {
+    pub fn new() -> Self {
+        Self::with_terminal(Terminal::open().ok())
+    }
+
+    /// A debugger that never opens the terminal, so `frame` returns straight away.
+    pub fn detached() -> Self {
+        Self::with_terminal(None)
+    }
+
+    fn with_terminal(terminal: Option<Terminal>) -> Self {
+        let mode = if terminal.is_some() {
+            Mode::Paused
+        } else {
+            Mode::Detached
+        };
+
+        Debugger {
+            terminal,
+            history: VecDeque::new(),
+            history_len: DEFAULT_HISTORY,
+            steps: 0,
+            cursor: None,
+            mode,
+            delay: DEFAULT_DELAY,
+            breakpoints: vec![],
+            viewport: Viewport::default(),
+            prompt: None,
+            message: String::new(),
+            finished: false,
+        }
+    }
+
+    /// How long each frame stays on screen while running.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// How many of the latest frames are kept for stepping back.
+    pub fn with_history(mut self, history_len: usize) -> Self {
+        self.history_len = history_len.max(1);
+        self
+    }
+
+    pub fn with_breakpoint(mut self, condition: Condition) -> Self {
+        self.breakpoints.push(condition);
+        self
+    }
+
+    /// Starts running instead of waiting on the first frame.
+    pub fn running(mut self) -> Self {
+        if self.mode == Mode::Paused {
+            self.mode = Mode::Running;
+        }
+        self
+    }
+
+    pub fn is_attached(&self) -> bool {
+        self.mode != Mode::Detached
+    }
+
+    /// The number of frames seen so far.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Shows the solver's next step, returning once the user lets it carry on.
+    pub fn frame(&mut self, grid: &CharGrid, vars: Vars) {
+        if !self.is_attached() {
+            self.steps += 1;
+            return;
+        }
+
+        self.record(grid, vars);
+        self.check_stops();
+
+        if self.mode != Mode::Paused {
+            self.draw();
+            let keys = self.read_keys(Some(self.delay));
+            for key in keys {
+                self.handle_key(key);
+            }
+        }
+
+        if self.mode == Mode::Paused {
+            self.wait();
+        }
+    }
+
+    /// Keeps the last frame on screen until the user leaves, then restores the terminal. For when
+    /// the solver is done.
+    pub fn finish(mut self) {
+        if !self.is_attached() || self.history.is_empty() {
+            return;
+        }
+
+        self.mode = Mode::Paused;
+        self.cursor = None;
+        self.finished = true;
+        self.message = format!("Finished after {} steps", self.steps);
+        self.wait();
+    }
+
+    fn record(&mut self, grid: &CharGrid, vars: Vars) {
+        self.steps += 1;
+        self.history.push_back(Frame {
+            step: self.steps,
+            grid: grid.clone(),
+            vars,
+        });
+
+        if self.history.len() > self.history_len {
+            self.history.pop_front();
+            if let Some(cursor) = self.cursor.as_mut() {
+                *cursor = cursor.saturating_sub(1);
+            }
+        }
+    }
+
+    /// Pauses a run that has reached its target step or a breakpoint.
+    fn check_stops(&mut self) {
+        let frame = self.history.back().expect("a frame was just recorded");
+
+        match self.mode {
+            Mode::RunTo(target) if frame.step >= target => {
+                self.mode = Mode::Paused;
+                self.message = format!("Reached step {}", frame.step);
+            }
+            Mode::Running | Mode::RunTo(_) => {
+                if let Some(condition) = self.breakpoints.iter().find(|c| c.matches(frame)) {
+                    self.message = format!("Breakpoint: {}", condition);
+                    self.mode = Mode::Paused;
+                }
+            }
+            Mode::Paused | Mode::Detached => {}
+        }
+    }
+
+    fn wait(&mut self) {
+        loop {
+            self.draw();
+            for key in self.read_keys(None) {
+                if self.handle_key(key) == Flow::Resume {
+                    return;
+                }
+            }
+        }
+    }
+
+    fn read_keys(&mut self, timeout: Option<Duration>) -> Vec<Key> {
+        let Some(terminal) = self.terminal.as_mut() else {
+            return vec![];
+        };
+        terminal.read_keys(timeout).unwrap_or_else(|err| {
+            self.message = format!("could not read keys: {}", err);
+            vec![]
+        })
+    }
+
+    fn current_index(&self) -> usize {
+        self.cursor
+            .unwrap_or_else(|| self.history.len().saturating_sub(1))
+    }
+
+    fn current(&self) -> Option<&Frame> {
+        self.history.get(self.current_index())
+    }
+
+    fn handle_key(&mut self, key: Key) -> Flow {
+        if self.prompt.is_some() {
+            return self.handle_prompt_key(key);
+        }
+
+        let (page_width, page_height) = (self.viewport.width as i64, self.viewport.height as i64);
+
+        match key {
+            Key::Char('n') | Key::Enter => return self.step_forward(),
+            Key::Char('p') => self.step_back(),
+            Key::Char(' ') | Key::Char('r') => {
+                if self.mode == Mode::Running {
+                    self.mode = Mode::Paused;
+                    self.message = "Paused".to_string();
+                } else {
+                    self.mode = Mode::Running;
+                    self.cursor = None;
+                    self.message.clear();
+                    return Flow::Resume;
+                }
+            }
+            Key::Char('g') => self.open_prompt(PromptKind::Step),
+            Key::Char('b') => self.open_prompt(PromptKind::Breakpoint),
+            Key::Char('c') => {
+                self.breakpoints.clear();
+                self.message = "Cleared breakpoints".to_string();
+            }
+            Key::Up | Key::Char('k') => self.scroll(0, -1),
+            Key::Down | Key::Char('j') => self.scroll(0, 1),
+            Key::Left | Key::Char('h') => self.scroll(-1, 0),
+            Key::Right | Key::Char('l') => self.scroll(1, 0),
+            Key::PageUp | Key::Char('K') => self.scroll(0, -page_height),
+            Key::PageDown | Key::Char('J') => self.scroll(0, page_height),
+            Key::Char('H') => self.scroll(-page_width, 0),
+            Key::Char('L') => self.scroll(page_width, 0),
+            Key::Home => self.scroll(i64::MIN / 2, i64::MIN / 2),
+            Key::End => self.scroll(i64::MAX / 2, i64::MAX / 2),
+            Key::Char('+') => {
+                self.delay /= 2;
+                self.message = format!("Delay: {}ms", self.delay.as_millis());
+            }
+            Key::Char('-') => {
+                self.delay = (self.delay * 2).max(Duration::from_millis(10));
+                self.message = format!("Delay: {}ms", self.delay.as_millis());
+            }
+            Key::Char('d') => {
+                self.mode = Mode::Detached;
+                self.terminal = None;
+                return Flow::Resume;
+            }
+            Key::Char('q') | Key::Interrupt if self.finished => return Flow::Resume,
+            Key::Char('q') => self.quit(QUIT_CODE),
+            Key::Interrupt => self.quit(INTERRUPT_CODE),
+            _ => {}
+        }
+
+        Flow::Stay
+    }
+
+    fn handle_prompt_key(&mut self, key: Key) -> Flow {
+        let prompt = self.prompt.as_mut().expect("a prompt is open");
+
+        match key {
+            Key::Char(ch) => prompt.input.push(ch),
+            Key::Backspace => {
+                prompt.input.pop();
+            }
+            Key::Esc => self.prompt = None,
+            Key::Interrupt if self.finished => return Flow::Resume,
+            Key::Interrupt => self.quit(INTERRUPT_CODE),
+            Key::Enter => {
+                let prompt = self.prompt.take().expect("a prompt is open");
+                return match prompt.kind {
+                    PromptKind::Step => match prompt.input.trim().parse() {
+                        Ok(step) => self.go_to(step),
+                        Err(_) => {
+                            self.message = format!("Not a step: {}", prompt.input);
+                            Flow::Stay
+                        }
+                    },
+                    PromptKind::Breakpoint => {
+                        match Condition::parse(&prompt.input) {
+                            Ok(condition) => {
+                                self.message = format!("Added breakpoint: {}", condition);
+                                self.breakpoints.push(condition);
+                            }
+                            Err(err) => self.message = format!("Bad breakpoint: {}", err),
+                        }
+                        Flow::Stay
+                    }
+                };
+            }
+            _ => {}
+        }
+
+        Flow::Stay
+    }
+
+    fn open_prompt(&mut self, kind: PromptKind) {
+        self.prompt = Some(Prompt {
+            kind,
+            input: String::new(),
+        });
+    }
+
+    /// Moves forward through the kept frames, letting the solver run one step once at the latest.
+    fn step_forward(&mut self) -> Flow {
+        self.message.clear();
+        match self.cursor {
+            Some(cursor) if cursor + 2 < self.history.len() => {
+                self.cursor = Some(cursor + 1);
+                Flow::Stay
+            }
+            Some(_) => {
+                self.cursor = None;
+                Flow::Stay
+            }
+            None => Flow::Resume,
+        }
+    }
+
+    fn step_back(&mut self) {
+        match self.current_index().checked_sub(1) {
+            Some(index) => {
+                self.cursor = Some(index);
+                self.message.clear();
+            }
+            None => self.message = "No earlier steps kept".to_string(),
+        }
+    }
+
+    /// Shows a kept step, or runs the solver on until a later one.
+    fn go_to(&mut self, step: usize) -> Flow {
+        if step > self.steps {
+            self.mode = Mode::RunTo(step);
+            self.cursor = None;
+            self.message = format!("Running to step {}", step);
+            return Flow::Resume;
+        }
+
+        match self.history.iter().position(|frame| frame.step == step) {
+            Some(index) => {
+                self.cursor = (index + 1 < self.history.len()).then_some(index);
+                self.message.clear();
+            }
+            None => self.message = format!("Step {} is no longer kept", step),
+        }
+        Flow::Stay
+    }
+
+    fn scroll(&mut self, dx: i64, dy: i64) {
+        if let Some(grid) = self.current().map(|frame| frame.grid.clone()) {
+            self.viewport.scroll(dx, dy, &grid);
+        }
+    }
+
+    /// Restores the terminal and ends the program before the solver is done.
+    fn quit(&mut self, code: i32) -> ! {
+        self.terminal = None;
+        process::exit(code);
+    }
+
+    fn draw(&mut self) {
+        let Some((columns, rows)) = self.terminal.as_ref().map(Terminal::size) else {
+            return;
+        };
+        let Some(frame) = self.current() else {
+            return;
+        };
+
+        let panel_width = PANEL_WIDTH.min(columns / 3);
+        let grid_columns = columns.saturating_sub(panel_width + SEPARATOR.chars().count());
+        let grid_rows = rows.saturating_sub(2);
+        let cell_width = frame.grid.cell_width().max(1);
+
+        let mut viewport = self.viewport;
+        viewport.resize(grid_columns / cell_width, grid_rows, &frame.grid);
+        let grid_lines = viewport.lines(&frame.grid);
+        let panel_lines = self.panel_lines(frame);
+
+        let mut screen = String::from("\x1B[H");
+        for row in 0..grid_rows {
+            let grid_line = grid_lines.get(row).map_or("", String::as_str);
+            let panel_line = panel_lines.get(row).map_or("", String::as_str);
+            let _ = writeln!(
+                screen,
+                "{:<grid_columns$}{}{}\x1B[K",
+                grid_line,
+                SEPARATOR,
+                truncate(panel_line, panel_width)
+            );
+        }
+
+        let mode = match self.mode {
+            Mode::Paused => "Paused".to_string(),
+            Mode::Running => "Running".to_string(),
+            Mode::RunTo(step) => format!("Running to {}", step),
+            Mode::Detached => "Detached".to_string(),
+        };
+        let status = format!(
+            "[{}] step {}/{}  view {},{}  delay {}ms  {}",
+            mode,
+            frame.step,
+            self.steps,
+            viewport.x,
+            viewport.y,
+            self.delay.as_millis(),
+            self.message
+        );
+        let _ = writeln!(screen, "{}\x1B[K", truncate(&status, columns));
+
+        let footer = match &self.prompt {
+            Some(prompt) => match prompt.kind {
+                PromptKind::Step => format!("Go to step: {}", prompt.input),
+                PromptKind::Breakpoint => format!("Break when (e.g. area > 30): {}", prompt.input),
+            },
+            None if self.finished => format!("{}  q done", HELP),
+            None => format!("{}  q quit", HELP),
+        };
+        let _ = write!(screen, "{}\x1B[K\x1B[J", truncate(&footer, columns));
+
+        self.viewport = viewport;
+        print!("{}", screen);
+        let _ = io::stdout().flush();
+    }
+
+    fn panel_lines(&self, frame: &Frame) -> Vec<String> {
+        let mut lines = vec![format!("Step {}", frame.step), String::new()];
+        lines.extend(
+            frame
+                .vars
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name, value)),
+        );
+
+        if !self.breakpoints.is_empty() {
+            lines.push(String::new());
+            lines.push("Breakpoints:".to_string());
+            lines.extend(self.breakpoints.iter().map(|c| format!("  {}", c)));
+        }
+
+        lines
+    }
+}
+
+impl Default for Debugger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn truncate(line: &str, width: usize) -> String {
+    line.chars().take(width).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A debugger with no terminal that still records frames, as `frame` would when attached.
+    fn recorded(steps: usize, history_len: usize) -> Debugger {
+        let mut debugger = Debugger::with_terminal(None).with_history(history_len);
+        debugger.mode = Mode::Paused;
+        for step in 1..=steps {
+            debugger.record(&CharGrid::new(2, 2), Vars::new().with("i", step * 10));
+        }
+        debugger
+    }
+
+    fn shown_step(debugger: &Debugger) -> usize {
+        debugger.current().unwrap().step
+    }
+
+    #[test]
+    fn vars() {
+        let mut vars = Vars::new().with("Area", 4).with("Valid", false);
+        vars.set("Area", 9);
+        assert_eq!(vars.get("area"), Some("9"));
+        assert_eq!(vars.iter().count(), 2);
+        assert_eq!(vars.get("max"), None);
+    }
+
+    #[test]
+    fn steps_back_through_history() {
+        let mut debugger = recorded(5, 3);
+        assert_eq!(shown_step(&debugger), 5);
+
+        debugger.handle_key(Key::Char('p'));
+        debugger.handle_key(Key::Char('p'));
+        assert_eq!(shown_step(&debugger), 3);
+        // Steps 1 and 2 have been dropped from the history
+        debugger.handle_key(Key::Char('p'));
+        assert_eq!(shown_step(&debugger), 3);
+
+        assert_eq!(debugger.handle_key(Key::Char('n')), Flow::Stay);
+        assert_eq!(debugger.handle_key(Key::Char('n')), Flow::Stay);
+        assert_eq!(shown_step(&debugger), 5);
+        assert_eq!(debugger.handle_key(Key::Char('n')), Flow::Resume);
+    }
+
+    #[test]
+    fn goes_to_step() {
+        let mut debugger = recorded(5, 10);
+        let go_to = |debugger: &mut Debugger, input: &str| {
+            debugger.handle_key(Key::Char('g'));
+            for ch in input.chars() {
+                debugger.handle_key(Key::Char(ch));
+            }
+            debugger.handle_key(Key::Enter)
+        };
+
+        assert_eq!(go_to(&mut debugger, "2"), Flow::Stay);
+        assert_eq!(shown_step(&debugger), 2);
+
+        assert_eq!(go_to(&mut debugger, "8"), Flow::Resume);
+        assert_eq!(debugger.mode, Mode::RunTo(8));
+        for step in 6..=8 {
+            debugger.record(&CharGrid::new(2, 2), Vars::new());
+            debugger.check_stops();
+            assert_eq!(debugger.mode == Mode::Paused, step == 8);
+        }
+    }
+
+    #[test]
+    fn quitting_after_finish_returns() {
+        let mut debugger = recorded(2, 10);
+        debugger.finished = true;
+        assert_eq!(debugger.handle_key(Key::Char('q')), Flow::Resume);
+        assert_eq!(debugger.handle_key(Key::Interrupt), Flow::Resume);
+    }
+
+    #[test]
+    fn stops_at_breakpoint() {
+        let mut debugger = recorded(1, 10);
+        debugger.handle_key(Key::Char('b'));
+        for ch in "i >= 30".chars() {
+            debugger.handle_key(Key::Char(ch));
+        }
+        debugger.handle_key(Key::Enter);
+        assert_eq!(debugger.breakpoints.len(), 1);
+
+        assert_eq!(debugger.handle_key(Key::Char(' ')), Flow::Resume);
+        for step in 2..=3 {
+            debugger.record(&CharGrid::new(2, 2), Vars::new().with("i", step * 10));
+            debugger.check_stops();
+        }
+        assert_eq!(debugger.mode, Mode::Paused);
+        assert_eq!(debugger.message, "Breakpoint: i >= 30");
+    }
+}
@@ -0,0 +1,241 @@
+#[cfg(unix)]
+pub use unix::Terminal;
+#[cfg(not(unix))]
+pub use unsupported::Terminal;
+
+/// A key press read from the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Key {
+    Char(char),
+    Up,
+    Down,
+    Left,
+    Right,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+    Enter,
+    Backspace,
+    Esc,
+    /// Ctrl-C, which arrives as a key because raw mode turns off signals
+    Interrupt,
+}
+
+/// Splits raw terminal input into keys, decoding the escape sequences for arrows and paging.
+/// Unknown escape sequences are dropped.
+pub fn parse_keys(input: &[u8]) -> Vec<Key> {
+    let input = String::from_utf8_lossy(input);
+    let mut chars = input.chars().peekable();
+    let mut keys = vec![];
+
+    while let Some(ch) = chars.next() {
+        let key = match ch {
+            '\x1B' if chars.peek() == Some(&'[') => {
+                chars.next();
+                let mut sequence = String::new();
+                while let Some(&next) = chars.peek() {
+                    chars.next();
+                    sequence.push(next);
+                    if next.is_ascii_alphabetic() || next == '~' {
+                        break;
+                    }
+                }
+                match sequence.as_str() {
+                    "A" => Key::Up,
+                    "B" => Key::Down,
+                    "C" => Key::Right,
+                    "D" => Key::Left,
+                    "H" | "1~" => Key::Home,
+                    "F" | "4~" => Key::End,
+                    "5~" => Key::PageUp,
+                    "6~" => Key::PageDown,
+                    _ => continue,
+                }
+            }
+            '\x1B' => Key::Esc,
+            '\r' | '\n' => Key::Enter,
+            '\x7F' | '\x08' => Key::Backspace,
+            '\x03' => Key::Interrupt,
+            ch => Key::Char(ch),
+        };
+        keys.push(key);
+    }
+
+    keys
+}
+
+#[cfg(unix)]
+mod unix {
+    use std::{
+        io::{self, Write},
+        mem::MaybeUninit,
+        time::Duration,
+    };
+
+    use crate::{
+        debugger::terminal::{Key, parse_keys},
+        io::{hide_cursor, show_cursor},
+    };
+
+    const ENTER_ALTERNATE_SCREEN: &str = "\x1B[?1049h";
+    const LEAVE_ALTERNATE_SCREEN: &str = "\x1B[?1049l";
+    const DEFAULT_SIZE: (usize, usize) = (80, 24);
+
+    /// Puts the terminal in raw mode on the alternate screen for as long as it lives, restoring it
+    /// when dropped.
+    pub struct Terminal {
+        original: libc::termios,
+    }
+
+    impl Terminal {
+        /// Fails when stdin or stdout is not a terminal, such as when output is piped.
+        pub fn open() -> io::Result<Self> {
+            // SAFETY: isatty only inspects the descriptors
+            let is_tty = unsafe {
+                libc::isatty(libc::STDIN_FILENO) == 1 && libc::isatty(libc::STDOUT_FILENO) == 1
+            };
+            if !is_tty {
+                return Err(io::Error::other("not a terminal"));
+            }
+
+            let mut original = MaybeUninit::uninit();
+            // SAFETY: tcgetattr fills in `original` when it succeeds
+            let original = unsafe {
+                if libc::tcgetattr(libc::STDIN_FILENO, original.as_mut_ptr()) != 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                original.assume_init()
+            };
+
+            let mut raw = original;
+            raw.c_lflag &= !(libc::ICANON | libc::ECHO | libc::ISIG);
+            raw.c_cc[libc::VMIN] = 1;
+            raw.c_cc[libc::VTIME] = 0;
+            // SAFETY: `raw` is a valid termios copied from the current settings
+            if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            print!("{}", ENTER_ALTERNATE_SCREEN);
+            hide_cursor();
+            io::stdout().flush()?;
+
+            Ok(Terminal { original })
+        }
+
+        /// The terminal's `(columns, rows)`, or 80x24 if it cannot be found.
+        pub fn size(&self) -> (usize, usize) {
+            let mut size = MaybeUninit::<libc::winsize>::zeroed();
+            // SAFETY: TIOCGWINSZ writes a winsize into `size`, which starts zeroed either way
+            let size = unsafe {
+                libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, size.as_mut_ptr());
+                size.assume_init()
+            };
+
+            if size.ws_col == 0 || size.ws_row == 0 {
+                return DEFAULT_SIZE;
+            }
+            (size.ws_col as usize, size.ws_row as usize)
+        }
+
+        /// Waits up to `timeout` for key presses, or forever when `None`. Returns no keys on timeout.
+        pub fn read_keys(&mut self, timeout: Option<Duration>) -> io::Result<Vec<Key>> {
+            let timeout = timeout.map_or(-1, |timeout| {
+                timeout.as_millis().min(i32::MAX as u128) as i32
+            });
+            let mut poll_fd = libc::pollfd {
+                fd: libc::STDIN_FILENO,
+                events: libc::POLLIN,
+                revents: 0,
+            };
+
+            // SAFETY: `poll_fd` is a single valid pollfd
+            match unsafe { libc::poll(&mut poll_fd, 1, timeout) } {
+                -1 => return Err(io::Error::last_os_error()),
+                0 => return Ok(vec![]),
+                _ => {}
+            }
+
+            let mut buffer = [0u8; 64];
+            // SAFETY: reads at most `buffer.len()` bytes into `buffer`
+            let n = unsafe {
+                libc::read(
+                    libc::STDIN_FILENO,
+                    buffer.as_mut_ptr() as *mut libc::c_void,
+                    buffer.len(),
+                )
+            };
+            if n < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(parse_keys(&buffer[..n as usize]))
+        }
+    }
+
+    impl Drop for Terminal {
+        fn drop(&mut self) {
+            // SAFETY: restores the settings read in `open`
+            unsafe {
+                libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.original);
+            }
+            show_cursor();
+            print!("{}", LEAVE_ALTERNATE_SCREEN);
+            let _ = io::stdout().flush();
+        }
+    }
+}
+
+/// Raw mode needs termios, so elsewhere the terminal never opens and the debugger stays detached.
+#[cfg(not(unix))]
+mod unsupported {
+    use std::{convert::Infallible, io, time::Duration};
+
+    use crate::debugger::terminal::Key;
+
+    pub struct Terminal {
+        never: Infallible,
+    }
+
+    impl Terminal {
+        pub fn open() -> io::Result<Self> {
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "terminal debugging needs a Unix terminal",
+            ))
+        }
+
+        pub fn size(&self) -> (usize, usize) {
+            match self.never {}
+        }
+
+        pub fn read_keys(&mut self, _timeout: Option<Duration>) -> io::Result<Vec<Key>> {
+            match self.never {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys() {
+        assert_eq!(
+            parse_keys(b"n\x1B[A\x1B[D\x1B[6~\r\x7F\x03"),
+            vec![
+                Key::Char('n'),
+                Key::Up,
+                Key::Left,
+                Key::PageDown,
+                Key::Enter,
+                Key::Backspace,
+                Key::Interrupt
+            ]
+        );
+        assert_eq!(parse_keys(b"\x1B"), vec![Key::Esc]);
+        assert_eq!(parse_keys(b"\x1B[99zq"), vec![Key::Char('q')]);
+        assert_eq!(parse_keys("é".as_bytes()), vec![Key::Char('é')]);
+    }
+}
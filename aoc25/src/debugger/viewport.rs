@@ -0,0 +1,89 @@
+use crate::char_grid::CharGrid;
+
+/// The window of a grid that fits on screen, measured in cells. Scrolling never moves it past the
+/// bottom right corner, so the window stays full when the grid is bigger than it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Viewport {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Viewport {
+    pub fn new(width: usize, height: usize) -> Self {
+        Viewport {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        }
+    }
+
+    pub fn scroll(&mut self, dx: i64, dy: i64, grid: &CharGrid) {
+        self.x = self.x.saturating_add_signed(dx as isize);
+        self.y = self.y.saturating_add_signed(dy as isize);
+        self.clamp(grid);
+    }
+
+    /// Changes the size of the window, keeping its top left corner where possible.
+    pub fn resize(&mut self, width: usize, height: usize, grid: &CharGrid) {
+        self.width = width;
+        self.height = height;
+        self.clamp(grid);
+    }
+
+    pub fn clamp(&mut self, grid: &CharGrid) {
+        self.x = self.x.min(grid.width().saturating_sub(self.width));
+        self.y = self.y.min(grid.height().saturating_sub(self.height));
+    }
+
+    /// The visible rows of `grid`, each cell right aligned to the grid's cell width as when the
+    /// grid is displayed.
+    pub fn lines(&self, grid: &CharGrid) -> Vec<String> {
+        let cell_width = grid.cell_width();
+        let max_x = (self.x + self.width).min(grid.width());
+        let max_y = (self.y + self.height).min(grid.height());
+
+        (self.y..max_y)
+            .map(|y| {
+                grid.get_row(y)[self.x.min(max_x)..max_x]
+                    .iter()
+                    .map(|c| format!("{:>cell_width$}", c))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scrolls_within_grid() {
+        let mut grid = CharGrid::from("abcd\nefgh\nijkl");
+        *grid.cell_width_mut() = 1;
+        let mut viewport = Viewport::new(2, 2);
+
+        assert_eq!(viewport.lines(&grid), vec!["ab", "ef"]);
+
+        viewport.scroll(1, 5, &grid);
+        assert_eq!((viewport.x, viewport.y), (1, 1));
+        assert_eq!(viewport.lines(&grid), vec!["fg", "jk"]);
+
+        viewport.scroll(10, -10, &grid);
+        assert_eq!(viewport.lines(&grid), vec!["cd", "gh"]);
+
+        viewport.resize(10, 10, &grid);
+        assert_eq!((viewport.x, viewport.y), (0, 0));
+        assert_eq!(viewport.lines(&grid).len(), 3);
+    }
+
+    #[test]
+    fn cell_width() {
+        let grid = CharGrid::from("ab\ncd");
+        let viewport = Viewport::new(5, 1);
+        assert_eq!(viewport.lines(&grid), vec!["  a  b"]);
+    }
+}
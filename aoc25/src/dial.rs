@@ -0,0 +1,156 @@
+use std::fmt::{self, Display, Formatter};
+
+/// A circular dial with positions `0..size` that wraps in both directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Dial {
+    size: i64,
+    position: i64,
+}
+
+impl Dial {
+    pub fn new(size: i64, position: i64) -> Self {
+        assert!(size > 0, "dial size must be positive");
+        Dial {
+            size,
+            position: position.rem_euclid(size),
+        }
+    }
+
+    pub fn size(&self) -> i64 {
+        self.size
+    }
+
+    pub fn position(&self) -> i64 {
+        self.position
+    }
+
+    /// The position after rotating by `delta` clicks, without moving the dial. Positive deltas
+    /// move towards higher numbers.
+    pub fn peek(&self, delta: i64) -> i64 {
+        (self.position + delta.rem_euclid(self.size)).rem_euclid(self.size)
+    }
+
+    /// Rotates by `delta` clicks and returns the new position.
+    pub fn rotate(&mut self, delta: i64) -> i64 {
+        self.position = self.peek(delta);
+        self.position
+    }
+
+    /// Counts how many clicks of a rotation by `delta` land on `mark`, including the final
+    /// position but not the starting one.
+    pub fn count_hits(&self, delta: i64, mark: i64) -> u64 {
+        let distance = delta.unsigned_abs();
+        let size = self.size as u64;
+        let mark = mark.rem_euclid(self.size);
+
+        // Clicks until the dial first reaches the mark
+        let offset = if delta >= 0 {
+            (mark - self.position).rem_euclid(self.size) as u64
+        } else {
+            (self.position - mark).rem_euclid(self.size) as u64
+        };
+        let first = if offset == 0 { size } else { offset };
+
+        if distance < first {
+            return 0;
+        }
+
+        1 + (distance - first) / size
+    }
+
+    /// Rotates by `delta` clicks and returns how many times `mark` was passed or landed on.
+    pub fn rotate_counting(&mut self, delta: i64, mark: i64) -> u64 {
+        let hits = self.count_hits(delta, mark);
+        self.rotate(delta);
+        hits
+    }
+}
+
+impl Display for Dial {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.position, self.size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic xorshift generator so failures are reproducible.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn range(&mut self, min: i64, max: i64) -> i64 {
+            min + (self.next() % (max - min + 1) as u64) as i64
+        }
+    }
+
+    fn simulate(dial: &Dial, delta: i64, mark: i64) -> (i64, u64) {
+        let mut position = dial.position();
+        let mut hits = 0;
+        for _ in 0..delta.abs() {
+            position = (position + delta.signum()).rem_euclid(dial.size());
+            if position == mark {
+                hits += 1;
+            }
+        }
+        (position, hits)
+    }
+
+    #[test]
+    fn rotate_wraps() {
+        let mut dial = Dial::new(100, 50);
+
+        assert_eq!(dial.rotate(-68), 82);
+        assert_eq!(dial.rotate(48), 30);
+        assert_eq!(dial.rotate(-30), 0);
+        assert_eq!(dial.rotate(-1), 99);
+        assert_eq!(dial.rotate(1), 0);
+        assert_eq!(dial.rotate(-1000), 0);
+    }
+
+    #[test]
+    fn count_hits() {
+        let dial = Dial::new(100, 50);
+        assert_eq!(dial.count_hits(50, 0), 1);
+        assert_eq!(dial.count_hits(49, 0), 0);
+        assert_eq!(dial.count_hits(1000, 0), 10);
+        assert_eq!(dial.count_hits(-1000, 0), 10);
+
+        // Starting on the mark does not count
+        let dial = Dial::new(100, 0);
+        assert_eq!(dial.count_hits(-5, 0), 0);
+        assert_eq!(dial.count_hits(100, 0), 1);
+        assert_eq!(dial.count_hits(0, 0), 0);
+    }
+
+    #[test]
+    fn rotate_counting_matches_simulation() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+        for _ in 0..10_000 {
+            let size = rng.range(1, 120);
+            let mut dial = Dial::new(size, rng.range(0, size - 1));
+            let delta = rng.range(-500, 500);
+            let mark = rng.range(0, size - 1);
+
+            let (expected_position, expected_hits) = simulate(&dial, delta, mark);
+            let description = format!("{} rotated by {} with mark {}", dial, delta, mark);
+
+            assert_eq!(
+                dial.rotate_counting(delta, mark),
+                expected_hits,
+                "{}",
+                description
+            );
+            assert_eq!(dial.position(), expected_position, "{}", description);
+        }
+    }
+}
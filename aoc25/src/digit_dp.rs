@@ -0,0 +1,429 @@
+use std::{collections::HashMap, hash::Hash, ops::RangeInclusive};
+
+use crate::math::digits::Digits;
+
+/// A property of a number's decimal digits, checked one digit at a time from the most
+/// significant digit.
+///
+/// Counting is fast when the number of distinct states is small, since every suffix is only
+/// explored once per state.
+pub trait DigitProperty {
+    type State: Clone + Eq + Hash;
+
+    /// The state before the first digit of a number with `len` digits.
+    fn start(&self, len: usize) -> Self::State;
+
+    /// The state after appending `digit`, or `None` if no number with this prefix can match.
+    fn next(&self, state: &Self::State, digit: u8) -> Option<Self::State>;
+
+    fn accepts(&self, state: &Self::State) -> bool;
+}
+
+/// Matches numbers whose digits never decrease, such as `1129`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NonDecreasing;
+
+impl DigitProperty for NonDecreasing {
+    type State = u8;
+
+    fn start(&self, _len: usize) -> u8 {
+        0
+    }
+
+    fn next(&self, last: &u8, digit: u8) -> Option<u8> {
+        (digit >= *last).then_some(digit)
+    }
+
+    fn accepts(&self, _last: &u8) -> bool {
+        true
+    }
+}
+
+/// Counts the numbers in `range` that match `property`.
+pub fn count<P, R>(property: &P, range: R) -> u64
+where
+    P: DigitProperty,
+    R: Into<RangeInclusive<u64>>,
+{
+    let (count, _) = count_and_sum(property, range);
+    count as u64
+}
+
+/// Sums the numbers in `range` that match `property`.
+pub fn sum<P, R>(property: &P, range: R) -> u128
+where
+    P: DigitProperty,
+    R: Into<RangeInclusive<u64>>,
+{
+    let (_, sum) = count_and_sum(property, range);
+    sum
+}
+
+fn count_and_sum<P, R>(property: &P, range: R) -> (u128, u128)
+where
+    P: DigitProperty,
+    R: Into<RangeInclusive<u64>>,
+{
+    let range = range.into();
+    let (start, end) = (*range.start(), *range.end());
+
+    if start > end {
+        return (0, 0);
+    }
+
+    let mut solver = Solver {
+        property,
+        memo: HashMap::new(),
+    };
+    let (end_count, end_sum) = solver.up_to(end);
+
+    if start == 0 {
+        return (end_count, end_sum);
+    }
+
+    let (start_count, start_sum) = solver.up_to(start - 1);
+    (end_count - start_count, end_sum - start_sum)
+}
+
+struct Solver<'a, P: DigitProperty> {
+    property: &'a P,
+    memo: HashMap<(usize, P::State), (u128, u128)>,
+}
+
+impl<P: DigitProperty> Solver<'_, P> {
+    /// Count and sum of the matching numbers in `0..=n`.
+    fn up_to(&mut self, n: u64) -> (u128, u128) {
+        // Zero is the one number with a leading zero, written as the single digit 0
+        let zero = self
+            .property
+            .next(&self.property.start(1), 0)
+            .is_some_and(|state| self.property.accepts(&state));
+        if n == 0 {
+            return (zero as u128, 0);
+        }
+
+        let digits: Vec<u8> = n.digits().map(|digit| digit as u8).collect();
+        let len = digits.len();
+        let (mut count, mut sum) = (zero as u128, 0);
+
+        // Every number shorter than n
+        for shorter in 1..len {
+            let (c, s) = self.of_len(shorter);
+            count += c;
+            sum += s;
+        }
+
+        // Numbers with as many digits as n, walking down the digits of n
+        let mut state = self.property.start(len);
+        let mut prefix = 0u128;
+
+        for (i, digit) in digits.iter().enumerate() {
+            let min = if i == 0 { 1 } else { 0 };
+            let remaining = len - i - 1;
+
+            for lower in min..*digit {
+                let Some(next) = self.property.next(&state, lower) else {
+                    continue;
+                };
+                let (c, s) = self.suffix(remaining, next);
+                count += c;
+                sum += (prefix * 10 + lower as u128) * 10u128.pow(remaining as u32) * c + s;
+            }
+
+            match self.property.next(&state, *digit) {
+                Some(next) => state = next,
+                None => return (count, sum),
+            }
+            prefix = prefix * 10 + *digit as u128;
+        }
+
+        if self.property.accepts(&state) {
+            count += 1;
+            sum += n as u128;
+        }
+
+        (count, sum)
+    }
+
+    /// Count and sum of all matching `len` digit numbers.
+    fn of_len(&mut self, len: usize) -> (u128, u128) {
+        let state = self.property.start(len);
+        let remaining = len - 1;
+        let place = 10u128.pow(remaining as u32);
+        let (mut count, mut sum) = (0, 0);
+
+        for digit in 1..=9 {
+            let Some(next) = self.property.next(&state, digit) else {
+                continue;
+            };
+            let (c, s) = self.suffix(remaining, next);
+            count += c;
+            sum += digit as u128 * place * c + s;
+        }
+
+        (count, sum)
+    }
+
+    /// Count and sum of every accepted `remaining` digit suffix, leading zeros included.
+    fn suffix(&mut self, remaining: usize, state: P::State) -> (u128, u128) {
+        if remaining == 0 {
+            return if self.property.accepts(&state) {
+                (1, 0)
+            } else {
+                (0, 0)
+            };
+        }
+
+        if let Some(result) = self.memo.get(&(remaining, state.clone())) {
+            return *result;
+        }
+
+        let place = 10u128.pow(remaining as u32 - 1);
+        let (mut count, mut sum) = (0, 0);
+
+        for digit in 0..=9 {
+            let Some(next) = self.property.next(&state, digit) else {
+                continue;
+            };
+            let (c, s) = self.suffix(remaining - 1, next);
+            count += c;
+            sum += digit as u128 * place * c + s;
+        }
+
+        self.memo.insert((remaining, state), (count, sum));
+        (count, sum)
+    }
+}
+
+/// How many times a block of digits must repeat for a number to count as periodic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Repeats {
+    /// The number is a block repeated exactly this many times, e.g. `Exactly(2)` matches `1212`
+    /// and `1111` but not `111`.
+    Exactly(u32),
+    /// The number is a block repeated at least this many times.
+    AtLeast(u32),
+}
+
+impl Repeats {
+    fn allows(&self, repeats: u32) -> bool {
+        match self {
+            Repeats::Exactly(n) => repeats == *n,
+            Repeats::AtLeast(n) => repeats >= *n,
+        }
+    }
+}
+
+/// Counts the periodic numbers in `range` without enumerating them.
+pub fn count_periodic<R: Into<RangeInclusive<u64>>>(range: R, repeats: Repeats) -> u64 {
+    let (count, _) = count_and_sum_periodic(range.into(), repeats);
+    count as u64
+}
+
+/// Sums the periodic numbers in `range` without enumerating them.
+pub fn sum_periodic<R: Into<RangeInclusive<u64>>>(range: R, repeats: Repeats) -> u128 {
+    let (_, sum) = count_and_sum_periodic(range.into(), repeats);
+    sum
+}
+
+fn count_and_sum_periodic(range: RangeInclusive<u64>, repeats: Repeats) -> (u128, u128) {
+    let (mut count, mut sum) = (0i128, 0i128);
+
+    for len in 1..=max_len(&range) {
+        if let Repeats::Exactly(n) = repeats {
+            // A block repeated n times is a single period, so no overlaps to account for
+            if n > 0 && len.is_multiple_of(n as usize) {
+                let (c, s) = with_period(&range, len, len / n as usize);
+                count += c as i128;
+                sum += s as i128;
+            }
+            continue;
+        }
+
+        // Sum the numbers whose smallest period is exactly k over the allowed periods, using
+        // Möbius inversion over the numbers with period k or any divisor of k
+        for k in divisors(len) {
+            if !repeats.allows((len / k) as u32) {
+                continue;
+            }
+
+            for j in divisors(k) {
+                let mu = mobius(k / j);
+                if mu == 0 {
+                    continue;
+                }
+                let (c, s) = with_period(&range, len, j);
+                count += mu as i128 * c as i128;
+                sum += mu as i128 * s as i128;
+            }
+        }
+    }
+
+    (count as u128, sum as u128)
+}
+
+/// Count and sum of the `len` digit numbers in `range` made of a `period` digit block repeated.
+fn with_period(range: &RangeInclusive<u64>, len: usize, period: usize) -> (u128, u128) {
+    let Some((first, last, multiplier)) = block_range(range, len, period) else {
+        return (0, 0);
+    };
+
+    let count = (last - first + 1) as u128;
+    let block_sum = (first as u128 + last as u128) * count / 2;
+    (count, block_sum * multiplier)
+}
+
+/// The blocks whose repetition lands in `range`, and the multiplier that repeats a block.
+fn block_range(range: &RangeInclusive<u64>, len: usize, period: usize) -> Option<(u64, u64, u128)> {
+    let multiplier: u128 = (0..len / period)
+        .map(|i| 10u128.pow((i * period) as u32))
+        .sum();
+
+    let min_block = 10u128.pow(period as u32 - 1);
+    let max_block = 10u128.pow(period as u32) - 1;
+    let start = *range.start() as u128;
+    let end = *range.end() as u128;
+
+    let first = min_block.max(start.div_ceil(multiplier));
+    let last = max_block.min(end / multiplier);
+
+    (first <= last).then_some((first as u64, last as u64, multiplier))
+}
+
+/// Iterates over the periodic numbers in `range` in ascending order, generating them directly
+/// from their repeated blocks.
+pub fn periodic_iter<R: Into<RangeInclusive<u64>>>(
+    range: R,
+    repeats: Repeats,
+) -> impl Iterator<Item = u64> {
+    let range = range.into();
+
+    (1..=max_len(&range)).flat_map(move |len| {
+        let mut numbers: Vec<u64> = divisors(len)
+            .into_iter()
+            .filter(|period| repeats.allows((len / period) as u32))
+            .filter_map(|period| block_range(&range, len, period))
+            .flat_map(|(first, last, multiplier)| {
+                (first..=last).map(move |block| (block as u128 * multiplier) as u64)
+            })
+            .collect();
+
+        numbers.sort_unstable();
+        numbers.dedup();
+        numbers
+    })
+}
+
+fn max_len(range: &RangeInclusive<u64>) -> usize {
+    if range.start() > range.end() {
+        return 0;
+    }
+    range.end().digit_count() as usize
+}
+
+fn divisors(n: usize) -> Vec<usize> {
+    (1..=n).filter(|d| n.is_multiple_of(*d)).collect()
+}
+
+fn mobius(mut n: usize) -> i32 {
+    let mut result = 1;
+    let mut p = 2;
+
+    while p * p <= n {
+        if n.is_multiple_of(p) {
+            n /= p;
+            if n.is_multiple_of(p) {
+                return 0;
+            }
+            result = -result;
+        }
+        p += 1;
+    }
+
+    if n > 1 { -result } else { result }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_periodic(n: u64, repeats: Repeats) -> bool {
+        let s = n.to_string();
+        let len = s.len();
+        (1..=len).filter(|k| len.is_multiple_of(*k)).any(|k| {
+            repeats.allows((len / k) as u32)
+                && s.as_bytes().chunks(k).all(|c| c == &s.as_bytes()[..k])
+        })
+    }
+
+    #[test]
+    fn non_decreasing() {
+        let expected: Vec<u64> = (0..=5000)
+            .filter(|n| {
+                let s = n.to_string();
+                s.as_bytes().windows(2).all(|w| w[0] <= w[1])
+            })
+            .collect();
+
+        assert_eq!(count(&NonDecreasing, 0..=5000), expected.len() as u64);
+        assert_eq!(count(&NonDecreasing, 0..=0), 1);
+        assert_eq!(
+            sum(&NonDecreasing, 123..=4567),
+            expected
+                .iter()
+                .filter(|n| (123..=4567).contains(*n))
+                .map(|n| *n as u128)
+                .sum()
+        );
+    }
+
+    #[test]
+    fn count_huge_range() {
+        // There are choose(len + 8, 8) non-decreasing numbers of each length, which sum to
+        // choose(28, 9) - 1 over lengths 1 to 19
+        assert_eq!(
+            count(&NonDecreasing, 1..=9_999_999_999_999_999_999),
+            6_906_899
+        );
+    }
+
+    #[test]
+    fn periodic_matches_brute_force() {
+        for repeats in [
+            Repeats::Exactly(2),
+            Repeats::Exactly(3),
+            Repeats::AtLeast(2),
+        ] {
+            for (start, end) in [
+                (1, 10_000),
+                (95, 115),
+                (998, 1012),
+                (1_188_511_880, 1_188_511_890),
+            ] {
+                let expected: Vec<u64> =
+                    (start..=end).filter(|n| is_periodic(*n, repeats)).collect();
+                let generated: Vec<u64> = periodic_iter(start..=end, repeats).collect();
+
+                assert_eq!(generated, expected, "{:?} {}-{}", repeats, start, end);
+                assert_eq!(count_periodic(start..=end, repeats), expected.len() as u64);
+                assert_eq!(
+                    sum_periodic(start..=end, repeats),
+                    expected.iter().map(|n| *n as u128).sum()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn periodic_example() {
+        assert_eq!(sum_periodic(11..=22, Repeats::Exactly(2)), 33);
+        assert_eq!(sum_periodic(95..=115, Repeats::Exactly(2)), 99);
+        assert_eq!(sum_periodic(95..=115, Repeats::AtLeast(2)), 99 + 111);
+    }
+
+    #[test]
+    fn mobius_values() {
+        let values: Vec<i32> = (1..=10).map(mobius).collect();
+        assert_eq!(values, vec![1, -1, -1, 0, -1, 1, -1, 0, 0, 1]);
+    }
+}
@@ -0,0 +1,255 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::math::vector::Vec2;
+
+/// One of the four orthogonal grid directions. `y` grows downwards, so `Up` is `(0, -1)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+/// One of the eight directions to the cells around a grid cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// Clockwise, starting at `Up`.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn iter() -> impl Iterator<Item = Direction> {
+        Direction::ALL.into_iter()
+    }
+
+    /// All four directions clockwise, starting at this one.
+    pub const fn clockwise_from(self) -> [Direction; 4] {
+        let start = self as usize;
+        [
+            Direction::ALL[start],
+            Direction::ALL[(start + 1) % 4],
+            Direction::ALL[(start + 2) % 4],
+            Direction::ALL[(start + 3) % 4],
+        ]
+    }
+
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn turn_around(self) -> Direction {
+        self.opposite()
+    }
+
+    pub fn opposite(self) -> Direction {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Direction::Left | Direction::Right)
+    }
+
+    pub fn is_vertical(self) -> bool {
+        !self.is_horizontal()
+    }
+
+    pub fn delta(self) -> Vec2 {
+        match self {
+            Direction::Up => Vec2::new(0, -1),
+            Direction::Right => Vec2::new(1, 0),
+            Direction::Down => Vec2::new(0, 1),
+            Direction::Left => Vec2::new(-1, 0),
+        }
+    }
+
+    /// The direction a unit step points in, if it is orthogonal.
+    pub fn from_delta(delta: Vec2) -> Option<Direction> {
+        Direction::iter().find(|direction| direction.delta() == delta)
+    }
+}
+
+impl Direction8 {
+    /// Clockwise, starting at `Up`.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    /// Clockwise, starting at `UpLeft`.
+    pub const DIAGONALS: [Direction8; 4] = [
+        Direction8::UpLeft,
+        Direction8::UpRight,
+        Direction8::DownRight,
+        Direction8::DownLeft,
+    ];
+
+    pub fn iter() -> impl Iterator<Item = Direction8> {
+        Direction8::ALL.into_iter()
+    }
+
+    /// Turns an eighth clockwise.
+    pub fn turn_right(self) -> Direction8 {
+        self.rotate(1)
+    }
+
+    /// Turns an eighth counter-clockwise.
+    pub fn turn_left(self) -> Direction8 {
+        self.rotate(7)
+    }
+
+    pub fn turn_right_90(self) -> Direction8 {
+        self.rotate(2)
+    }
+
+    pub fn turn_left_90(self) -> Direction8 {
+        self.rotate(6)
+    }
+
+    pub fn turn_around(self) -> Direction8 {
+        self.opposite()
+    }
+
+    pub fn opposite(self) -> Direction8 {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    pub fn delta(self) -> Vec2 {
+        match self {
+            Direction8::Up => Vec2::new(0, -1),
+            Direction8::UpRight => Vec2::new(1, -1),
+            Direction8::Right => Vec2::new(1, 0),
+            Direction8::DownRight => Vec2::new(1, 1),
+            Direction8::Down => Vec2::new(0, 1),
+            Direction8::DownLeft => Vec2::new(-1, 1),
+            Direction8::Left => Vec2::new(-1, 0),
+            Direction8::UpLeft => Vec2::new(-1, -1),
+        }
+    }
+
+    pub fn from_delta(delta: Vec2) -> Option<Direction8> {
+        Direction8::iter().find(|direction| direction.delta() == delta)
+    }
+
+    /// The orthogonal direction, if this is not a diagonal.
+    pub fn to_direction(self) -> Option<Direction> {
+        Direction::from_delta(self.delta())
+    }
+
+    fn rotate(self, eighths: usize) -> Direction8 {
+        Direction8::ALL[(self as usize + eighths) % 8]
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(value: Direction) -> Self {
+        Direction8::ALL[value as usize * 2]
+    }
+}
+
+impl From<Direction> for Vec2 {
+    fn from(value: Direction) -> Self {
+        value.delta()
+    }
+}
+
+impl From<Direction8> for Vec2 {
+    fn from(value: Direction8) -> Self {
+        value.delta()
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let arrow = match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        };
+        write!(f, "{}", arrow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Down.turn_around(), Direction::Up);
+        assert_eq!(
+            Direction::Left.clockwise_from(),
+            [
+                Direction::Left,
+                Direction::Up,
+                Direction::Right,
+                Direction::Down
+            ]
+        );
+
+        assert_eq!(Direction8::Up.turn_right(), Direction8::UpRight);
+        assert_eq!(Direction8::Up.turn_left(), Direction8::UpLeft);
+        assert_eq!(Direction8::UpLeft.turn_right_90(), Direction8::UpRight);
+        assert_eq!(Direction8::Right.turn_left_90(), Direction8::Up);
+        assert_eq!(Direction8::DownLeft.opposite(), Direction8::UpRight);
+    }
+
+    #[test]
+    fn deltas_agree_with_turns() {
+        for direction in Direction::iter() {
+            assert_eq!(
+                direction.turn_right().delta(),
+                direction.delta().rotate_right()
+            );
+            assert_eq!(direction.opposite().delta(), -direction.delta());
+            assert_eq!(Direction::from_delta(direction.delta()), Some(direction));
+            assert_eq!(Direction8::from(direction).delta(), direction.delta());
+        }
+
+        for direction in Direction8::iter() {
+            assert_eq!(direction.opposite().delta(), -direction.delta());
+            assert_eq!(direction.delta().chebyshev(), 1);
+            assert_eq!(direction.is_diagonal(), direction.to_direction().is_none());
+        }
+    }
+
+    #[test]
+    fn defined_order() {
+        let deltas: Vec<(i64, i64)> = Direction::iter().map(|d| d.delta().into()).collect();
+        assert_eq!(deltas, vec![(0, -1), (1, 0), (0, 1), (-1, 0)]);
+        assert_eq!(Direction8::iter().filter(|d| d.is_diagonal()).count(), 4);
+        assert!(Direction8::DIAGONALS.iter().all(|d| d.is_diagonal()));
+    }
+}
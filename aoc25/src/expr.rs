@@ -0,0 +1,376 @@
+use std::fmt::{self, Display, Formatter};
+
+/// A binary arithmetic operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl Operator {
+    pub const ALL: [Operator; 4] = [
+        Operator::Add,
+        Operator::Subtract,
+        Operator::Multiply,
+        Operator::Divide,
+    ];
+
+    pub fn symbol(self) -> char {
+        match self {
+            Operator::Add => '+',
+            Operator::Subtract => '-',
+            Operator::Multiply => '*',
+            Operator::Divide => '/',
+        }
+    }
+
+    pub fn from_symbol(symbol: char) -> Option<Self> {
+        Operator::ALL.into_iter().find(|op| op.symbol() == symbol)
+    }
+
+    /// Applies the operator with overflow checks. Division truncates towards zero.
+    pub fn apply(self, lhs: i64, rhs: i64) -> Result<i64, ExprError> {
+        match self {
+            Operator::Add => lhs.checked_add(rhs).ok_or(ExprError::Overflow),
+            Operator::Subtract => lhs.checked_sub(rhs).ok_or(ExprError::Overflow),
+            Operator::Multiply => lhs.checked_mul(rhs).ok_or(ExprError::Overflow),
+            Operator::Divide if rhs == 0 => Err(ExprError::DivisionByZero),
+            Operator::Divide => lhs.checked_div(rhs).ok_or(ExprError::Overflow),
+        }
+    }
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+/// Which operators are allowed and how tightly they bind. Higher levels bind tighter, and
+/// operators on the same level are evaluated left to right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Precedence {
+    levels: [Option<u8>; Operator::ALL.len()],
+}
+
+impl Precedence {
+    /// No operators allowed; add them with [`Precedence::with`].
+    pub fn empty() -> Self {
+        Precedence {
+            levels: [None; Operator::ALL.len()],
+        }
+    }
+
+    /// The usual rules: `*` and `/` bind tighter than `+` and `-`.
+    pub fn standard() -> Self {
+        Precedence::empty()
+            .with(Operator::Add, 1)
+            .with(Operator::Subtract, 1)
+            .with(Operator::Multiply, 2)
+            .with(Operator::Divide, 2)
+    }
+
+    /// Every operator binds equally, so expressions are evaluated strictly left to right.
+    pub fn flat() -> Self {
+        Operator::ALL
+            .into_iter()
+            .fold(Precedence::empty(), |table, op| table.with(op, 1))
+    }
+
+    /// `+` and `-` bind tighter than `*` and `/`.
+    pub fn inverted() -> Self {
+        Precedence::empty()
+            .with(Operator::Add, 2)
+            .with(Operator::Subtract, 2)
+            .with(Operator::Multiply, 1)
+            .with(Operator::Divide, 1)
+    }
+
+    pub fn with(mut self, op: Operator, level: u8) -> Self {
+        self.levels[op as usize] = Some(level);
+        self
+    }
+
+    pub fn without(mut self, op: Operator) -> Self {
+        self.levels[op as usize] = None;
+        self
+    }
+
+    /// The binding level of `op`, or `None` if it is not allowed.
+    pub fn level(&self, op: Operator) -> Option<u8> {
+        self.levels[op as usize]
+    }
+}
+
+impl Default for Precedence {
+    fn default() -> Self {
+        Precedence::standard()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Token {
+    Number(i64),
+    Operator(Operator),
+    OpenParen,
+    CloseParen,
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Number(value) => write!(f, "{}", value),
+            Token::Operator(op) => write!(f, "{}", op),
+            Token::OpenParen => write!(f, "("),
+            Token::CloseParen => write!(f, ")"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExprError {
+    InvalidChar(char),
+    UnsupportedOperator(Operator),
+    UnexpectedToken(Token),
+    UnexpectedEnd,
+    Overflow,
+    DivisionByZero,
+}
+
+impl Display for ExprError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ExprError::InvalidChar(ch) => write!(f, "invalid char: {}", ch),
+            ExprError::UnsupportedOperator(op) => write!(f, "unsupported operator: {}", op),
+            ExprError::UnexpectedToken(token) => write!(f, "unexpected token: {}", token),
+            ExprError::UnexpectedEnd => write!(f, "unexpected end of expression"),
+            ExprError::Overflow => write!(f, "arithmetic overflow"),
+            ExprError::DivisionByZero => write!(f, "division by zero"),
+        }
+    }
+}
+
+impl std::error::Error for ExprError {}
+
+/// A parsed expression tree.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Expr {
+    Number(i64),
+    Negate(Box<Expr>),
+    Binary {
+        op: Operator,
+        lhs: Box<Expr>,
+        rhs: Box<Expr>,
+    },
+}
+
+impl Expr {
+    pub fn parse(input: &str, precedence: &Precedence) -> Result<Expr, ExprError> {
+        let tokens = tokenize(input)?;
+        let mut parser = Parser {
+            tokens: &tokens,
+            pos: 0,
+            precedence,
+        };
+
+        let expr = parser.parse_expr(0)?;
+        match parser.peek() {
+            Some(token) => Err(ExprError::UnexpectedToken(token)),
+            None => Ok(expr),
+        }
+    }
+
+    pub fn eval(&self) -> Result<i64, ExprError> {
+        match self {
+            Expr::Number(value) => Ok(*value),
+            Expr::Negate(expr) => expr.eval()?.checked_neg().ok_or(ExprError::Overflow),
+            Expr::Binary { op, lhs, rhs } => op.apply(lhs.eval()?, rhs.eval()?),
+        }
+    }
+}
+
+/// Prints the tree fully parenthesized, so the grouping chosen by the parser is visible.
+impl Display for Expr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Number(value) => write!(f, "{}", value),
+            Expr::Negate(expr) => write!(f, "-{}", expr),
+            Expr::Binary { op, lhs, rhs } => write!(f, "({} {} {})", lhs, op, rhs),
+        }
+    }
+}
+
+/// Parses and evaluates `input` in one go.
+pub fn evaluate(input: &str, precedence: &Precedence) -> Result<i64, ExprError> {
+    Expr::parse(input, precedence)?.eval()
+}
+
+pub fn tokenize(input: &str) -> Result<Vec<Token>, ExprError> {
+    let mut tokens = vec![];
+    let mut chars = input.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        let token = match ch {
+            _ if ch.is_whitespace() => continue,
+            '(' => Token::OpenParen,
+            ')' => Token::CloseParen,
+            _ if ch.is_ascii_digit() => {
+                let mut value = ch.to_digit(10).expect("digit") as i64;
+                while let Some(digit) = chars.peek().and_then(|ch| ch.to_digit(10)) {
+                    chars.next();
+                    value = value
+                        .checked_mul(10)
+                        .and_then(|value| value.checked_add(digit as i64))
+                        .ok_or(ExprError::Overflow)?;
+                }
+                Token::Number(value)
+            }
+            _ => Token::Operator(Operator::from_symbol(ch).ok_or(ExprError::InvalidChar(ch))?),
+        };
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+/// Precedence climbing parser. Every operator is left associative.
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    precedence: &'a Precedence,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<Token> {
+        self.tokens.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Result<Token, ExprError> {
+        let token = self.peek().ok_or(ExprError::UnexpectedEnd)?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    /// Parses operators binding at `min_level` or tighter. The minimum is wider than a level so
+    /// that one past the highest level still fits.
+    fn parse_expr(&mut self, min_level: u16) -> Result<Expr, ExprError> {
+        let mut lhs = self.parse_operand()?;
+
+        while let Some(Token::Operator(op)) = self.peek() {
+            let level = self
+                .precedence
+                .level(op)
+                .ok_or(ExprError::UnsupportedOperator(op))?;
+            if u16::from(level) < min_level {
+                break;
+            }
+
+            self.pos += 1;
+            let rhs = self.parse_expr(u16::from(level) + 1)?;
+            lhs = Expr::Binary {
+                op,
+                lhs: Box::new(lhs),
+                rhs: Box::new(rhs),
+            };
+        }
+
+        Ok(lhs)
+    }
+
+    fn parse_operand(&mut self) -> Result<Expr, ExprError> {
+        match self.next()? {
+            Token::Number(value) => Ok(Expr::Number(value)),
+            Token::Operator(Operator::Subtract) => {
+                Ok(Expr::Negate(Box::new(self.parse_operand()?)))
+            }
+            Token::OpenParen => {
+                let expr = self.parse_expr(0)?;
+                match self.next()? {
+                    Token::CloseParen => Ok(expr),
+                    token => Err(ExprError::UnexpectedToken(token)),
+                }
+            }
+            token => Err(ExprError::UnexpectedToken(token)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIXED: &str = "1 + 2 * 3 + 4 * 5 + 6";
+    const NESTED: &str = "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2";
+
+    #[test]
+    fn precedence_tables() {
+        assert_eq!(evaluate(MIXED, &Precedence::standard()), Ok(33));
+        assert_eq!(evaluate(MIXED, &Precedence::flat()), Ok(71));
+        assert_eq!(evaluate(MIXED, &Precedence::inverted()), Ok(231));
+
+        assert_eq!(evaluate(NESTED, &Precedence::flat()), Ok(13632));
+        assert_eq!(evaluate(NESTED, &Precedence::inverted()), Ok(23340));
+    }
+
+    #[test]
+    fn left_to_right_within_a_level() {
+        assert_eq!(evaluate("10 - 4 - 3", &Precedence::standard()), Ok(3));
+        assert_eq!(evaluate("100 / 10 / 5", &Precedence::standard()), Ok(2));
+        assert_eq!(evaluate("2 * (3 + 4)", &Precedence::standard()), Ok(14));
+        assert_eq!(evaluate("-3 * -(2 - 5)", &Precedence::standard()), Ok(-9));
+    }
+
+    #[test]
+    fn highest_level() {
+        let precedence = Precedence::standard()
+            .with(Operator::Subtract, u8::MAX)
+            .with(Operator::Add, u8::MAX)
+            .with(Operator::Multiply, u8::MAX - 1);
+
+        assert_eq!(evaluate("10 - 4 - 3", &precedence), Ok(3));
+        assert_eq!(evaluate("2 * 3 + 4", &precedence), Ok(14));
+    }
+
+    #[test]
+    fn prints_grouping() {
+        let expr = Expr::parse("1 + 2 * 3", &Precedence::standard()).unwrap();
+        assert_eq!(expr.to_string(), "(1 + (2 * 3))");
+
+        let expr = Expr::parse("1 + 2 * 3", &Precedence::flat()).unwrap();
+        assert_eq!(expr.to_string(), "((1 + 2) * 3)");
+        assert!(matches!(
+            expr,
+            Expr::Binary {
+                op: Operator::Multiply,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn errors() {
+        let table = Precedence::standard();
+        let add_only = Precedence::empty().with(Operator::Add, 1);
+
+        assert_eq!(evaluate("1 + x", &table), Err(ExprError::InvalidChar('x')));
+        assert_eq!(evaluate("(1 + 2", &table), Err(ExprError::UnexpectedEnd));
+        assert_eq!(
+            evaluate("1 2", &table),
+            Err(ExprError::UnexpectedToken(Token::Number(2)))
+        );
+        assert_eq!(
+            evaluate("1 + 2 * 3", &add_only),
+            Err(ExprError::UnsupportedOperator(Operator::Multiply))
+        );
+        assert_eq!(
+            evaluate("1 / (2 - 2)", &table),
+            Err(ExprError::DivisionByZero)
+        );
+        assert_eq!(
+            evaluate("9223372036854775807 + 1", &table),
+            Err(ExprError::Overflow)
+        );
+    }
+}
@@ -0,0 +1,337 @@
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const YEAR: u32 = 2025;
+const BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "aoc25 input fetcher";
+const INPUT_FILE: &str = "input.txt";
+const PUZZLE_FILE: &str = "puzzle.html";
+const INDEX_FILE: &str = "index.html";
+const CODE_START: &str = "<pre><code>";
+const CODE_END: &str = "</code></pre>";
+
+/// Fetches a path such as `2025/day/1/input` from the puzzle site.
+pub trait HttpClient {
+    fn get(&self, path: &str, session: &str) -> io::Result<String>;
+}
+
+/// Downloads with the `curl` binary, which keeps TLS out of this crate's dependencies.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CurlClient;
+
+impl HttpClient for CurlClient {
+    /// The session cookie goes to curl as a config file on stdin rather than as an argument, so
+    /// other users cannot read it from the process list.
+    fn get(&self, path: &str, session: &str) -> io::Result<String> {
+        let mut child = Command::new("curl")
+            .args([
+                "--silent",
+                "--show-error",
+                "--fail",
+                "--user-agent",
+                USER_AGENT,
+                "--config",
+                "-",
+            ])
+            .arg(format!("{}/{}", BASE_URL, path))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(curl_config(session).as_bytes())?;
+        let output = child.wait_with_output()?;
+
+        if !output.status.success() {
+            return Err(io::Error::other(format!(
+                "could not download {}: {}",
+                path,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        String::from_utf8(output.stdout)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "response is not UTF-8"))
+    }
+}
+
+/// A curl config line sending the session cookie, quoted so any char in the token is kept.
+fn curl_config(session: &str) -> String {
+    let escaped = session
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r");
+    format!("cookie = \"session={}\"\n", escaped)
+}
+
+/// Serves paths from files in a directory, as a stand-in for the site. Like a static file server,
+/// a path that names a directory is served from its `index.html`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FixtureClient {
+    dir: PathBuf,
+}
+
+impl FixtureClient {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        FixtureClient { dir: dir.into() }
+    }
+}
+
+impl HttpClient for FixtureClient {
+    fn get(&self, path: &str, _session: &str) -> io::Result<String> {
+        let path = self.dir.join(path);
+        if path.is_dir() {
+            fs::read_to_string(path.join(INDEX_FILE))
+        } else {
+            fs::read_to_string(path)
+        }
+    }
+}
+
+/// Downloads puzzle inputs and pages, keeping a copy on disk so each is only fetched once.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Fetcher<C: HttpClient> {
+    client: C,
+    cache_dir: PathBuf,
+    session: Option<String>,
+    year: u32,
+}
+
+impl Fetcher<CurlClient> {
+    /// Uses `curl` with the session token from the `AOC_SESSION` environment variable.
+    pub fn from_env(cache_dir: impl Into<PathBuf>) -> Self {
+        let session = env::var(SESSION_VAR)
+            .ok()
+            .map(|session| session.trim().to_string());
+        Fetcher::new(CurlClient, cache_dir, session)
+    }
+}
+
+impl<C: HttpClient> Fetcher<C> {
+    pub fn new(client: C, cache_dir: impl Into<PathBuf>, session: Option<String>) -> Self {
+        Fetcher {
+            client,
+            cache_dir: cache_dir.into(),
+            session,
+            year: YEAR,
+        }
+    }
+
+    pub fn with_year(mut self, year: u32) -> Self {
+        self.year = year;
+        self
+    }
+
+    pub fn input(&self, day: u32) -> io::Result<String> {
+        self.cached(day, INPUT_FILE, &format!("{}/day/{}/input", self.year, day))
+    }
+
+    /// The code blocks on the puzzle page, which hold the examples. The first is usually the
+    /// example input.
+    pub fn examples(&self, day: u32) -> io::Result<Vec<String>> {
+        let page = self.cached(day, PUZZLE_FILE, &format!("{}/day/{}", self.year, day))?;
+        Ok(code_blocks(&page))
+    }
+
+    /// Whether the input for `day` is already on disk.
+    pub fn is_cached(&self, day: u32) -> bool {
+        self.cache_path(day, INPUT_FILE).exists()
+    }
+
+    fn cached(&self, day: u32, file_name: &str, path: &str) -> io::Result<String> {
+        let cache_path = self.cache_path(day, file_name);
+
+        match fs::read_to_string(&cache_path) {
+            Ok(contents) => return Ok(contents),
+            Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+            _ => {}
+        }
+
+        let session = self.session.as_deref().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("{} is not set", SESSION_VAR),
+            )
+        })?;
+        let contents = self.client.get(path, session)?;
+
+        fs::create_dir_all(cache_path.parent().expect("cache path has a parent"))?;
+        fs::write(&cache_path, &contents)?;
+        Ok(contents)
+    }
+
+    fn cache_path(&self, day: u32, file_name: &str) -> PathBuf {
+        self.cache_dir
+            .join(self.year.to_string())
+            .join(format!("day{}", day))
+            .join(file_name)
+    }
+}
+
+/// Writes `contents` to `path` unless the file already has something in it, so hand-edited files
+/// are never overwritten. Returns whether the file was written.
+pub fn write_if_empty(path: &Path, contents: &str) -> io::Result<bool> {
+    match fs::read_to_string(path) {
+        Ok(existing) if !existing.trim().is_empty() => Ok(false),
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => fs::write(path, contents).map(|_| true),
+    }
+}
+
+fn code_blocks(page: &str) -> Vec<String> {
+    let mut blocks = vec![];
+    let mut rest = page;
+
+    while let Some(start) = rest.find(CODE_START) {
+        rest = &rest[start + CODE_START.len()..];
+        let Some(end) = rest.find(CODE_END) else {
+            break;
+        };
+        blocks.push(unescape_html(&strip_tags(&rest[..end])));
+        rest = &rest[end + CODE_END.len()..];
+    }
+
+    blocks
+}
+
+/// Drops inline markup such as `<em>` that highlights parts of an example.
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for ch in html.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(ch),
+            _ => {}
+        }
+    }
+
+    text
+}
+
+fn unescape_html(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn session_cookie_config() {
+        assert_eq!(curl_config("53616c74"), "cookie = \"session=53616c74\"\n");
+        // Quotes and line breaks cannot end the value or start another option
+        assert_eq!(
+            curl_config("a\"b\\c\nurl = x"),
+            "cookie = \"session=a\\\"b\\\\c\\nurl = x\"\n"
+        );
+    }
+
+    const PAGE: &str = "<article><p>For example:</p>\n<pre><code>1 &lt; 2\n<em>3</em> &amp; 4\n</code></pre>\n<p>Then</p><pre><code>xyz</code></pre></article>";
+
+    /// Counts requests so tests can check nothing is downloaded twice.
+    struct CountingClient {
+        inner: FixtureClient,
+        requests: Cell<usize>,
+    }
+
+    impl HttpClient for &CountingClient {
+        fn get(&self, path: &str, session: &str) -> io::Result<String> {
+            self.requests.set(self.requests.get() + 1);
+            self.inner.get(path, session)
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc25-fetch-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn fixtures(dir: &Path) -> FixtureClient {
+        fs::create_dir_all(dir.join("2025/day/3")).unwrap();
+        fs::write(dir.join("2025/day/3/input"), "987654321111111\n").unwrap();
+        fs::write(dir.join("2025/day/3/index.html"), PAGE).unwrap();
+        FixtureClient::new(dir)
+    }
+
+    #[test]
+    fn input_downloaded_once() {
+        let dir = temp_dir("once");
+        let client = CountingClient {
+            inner: fixtures(&dir.join("site")),
+            requests: Cell::new(0),
+        };
+        let fetcher = Fetcher::new(&client, dir.join("cache"), Some("token".to_string()));
+
+        assert!(!fetcher.is_cached(3));
+        assert_eq!(fetcher.input(3).unwrap(), "987654321111111\n");
+        assert_eq!(fetcher.input(3).unwrap(), "987654321111111\n");
+        assert_eq!(client.requests.get(), 1);
+        assert!(fetcher.is_cached(3));
+
+        // Cached days need no session
+        let offline = Fetcher::new(&client, dir.join("cache"), None);
+        assert_eq!(offline.input(3).unwrap(), "987654321111111\n");
+        assert_eq!(client.requests.get(), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn missing_session() {
+        let dir = temp_dir("session");
+        let fetcher = Fetcher::new(fixtures(&dir.join("site")), dir.join("cache"), None);
+
+        let error = fetcher.input(3).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::PermissionDenied);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn examples_from_page() {
+        let dir = temp_dir("examples");
+        let fetcher = Fetcher::new(
+            fixtures(&dir.join("site")),
+            dir.join("cache"),
+            Some("token".to_string()),
+        );
+        assert_eq!(fetcher.examples(3).unwrap()[0], "1 < 2\n3 & 4\n");
+        fs::remove_dir_all(dir).unwrap();
+
+        assert_eq!(code_blocks(PAGE), vec!["1 < 2\n3 & 4\n", "xyz"]);
+        assert!(code_blocks("<p>no code</p>").is_empty());
+    }
+
+    #[test]
+    fn write_only_empty_files() {
+        let dir = temp_dir("write");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("input.txt");
+
+        assert!(write_if_empty(&path, "a").unwrap());
+        assert!(!write_if_empty(&path, "b").unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
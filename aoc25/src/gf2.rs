@@ -0,0 +1,231 @@
+use std::iter;
+
+use crate::bitset::BitSet;
+
+/// A dense matrix over GF(2), stored row by row.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Matrix {
+    cols: usize,
+    rows: Vec<BitSet>,
+}
+
+impl Matrix {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Matrix {
+            cols,
+            rows: vec![BitSet::new(cols); rows],
+        }
+    }
+
+    /// Builds a `rows` x `columns.len()` matrix from its columns.
+    pub fn from_columns(rows: usize, columns: &[BitSet]) -> Self {
+        let mut matrix = Matrix::new(rows, columns.len());
+        for (x, column) in columns.iter().enumerate() {
+            assert_eq!(column.len(), rows, "column {} has the wrong length", x);
+            for y in column.ones_iter() {
+                matrix.set(y, x, true);
+            }
+        }
+        matrix
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        self.rows[row].get(col)
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: bool) {
+        self.rows[row].set(col, value);
+    }
+
+    pub fn row(&self, row: usize) -> &BitSet {
+        &self.rows[row]
+    }
+
+    pub fn rank(&self) -> usize {
+        let mut rows = self.rows.clone();
+        eliminate(&mut rows, self.cols).len()
+    }
+
+    /// Solves `self * x = rhs`, returning `None` if the system is inconsistent.
+    pub fn solve(&self, rhs: &BitSet) -> Option<Solution> {
+        assert_eq!(rhs.len(), self.num_rows(), "rhs has the wrong length");
+
+        let cols = self.cols;
+
+        // Augment each row with its rhs bit
+        let mut rows: Vec<BitSet> = self
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(y, row)| row.iter().chain(iter::once(rhs.get(y))).collect())
+            .collect();
+
+        let pivots = eliminate(&mut rows, cols);
+
+        if rows[pivots.len()..].iter().any(|row| row.get(cols)) {
+            // 0 = 1
+            return None;
+        }
+
+        let mut particular = BitSet::new(cols);
+        for (row, pivot) in pivots.iter().enumerate() {
+            particular.set(*pivot, rows[row].get(cols));
+        }
+
+        let null_space = (0..cols)
+            .filter(|col| !pivots.contains(col))
+            .map(|free| {
+                let mut basis = BitSet::new(cols);
+                basis.set(free, true);
+                for (row, pivot) in pivots.iter().enumerate() {
+                    basis.set(*pivot, rows[row].get(free));
+                }
+                basis
+            })
+            .collect();
+
+        Some(Solution {
+            particular,
+            null_space,
+        })
+    }
+}
+
+/// Reduces `rows` to reduced row echelon form over the first `cols` columns, returning the pivot
+/// column of each leading row.
+fn eliminate(rows: &mut [BitSet], cols: usize) -> Vec<usize> {
+    let mut pivots = vec![];
+
+    for col in 0..cols {
+        let rank = pivots.len();
+        let Some(found) = (rank..rows.len()).find(|y| rows[*y].get(col)) else {
+            continue;
+        };
+
+        rows.swap(rank, found);
+        let pivot_row = rows[rank].clone();
+
+        for (y, row) in rows.iter_mut().enumerate() {
+            if y != rank && row.get(col) {
+                *row ^= &pivot_row;
+            }
+        }
+
+        pivots.push(col);
+    }
+
+    pivots
+}
+
+/// The solution set of a linear system: a particular solution plus any combination of the null
+/// space basis.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Solution {
+    pub particular: BitSet,
+    pub null_space: Vec<BitSet>,
+}
+
+impl Solution {
+    /// The number of solutions, or `None` if it does not fit in a `u128`.
+    pub fn count(&self) -> Option<u128> {
+        1u128.checked_shl(self.null_space.len() as u32)
+    }
+
+    /// Iterates over every solution in Gray code order. There are `2^k` of them for a null space
+    /// of dimension `k`.
+    pub fn solutions_iter(&self) -> impl Iterator<Item = BitSet> + '_ {
+        let mut current = self.particular.clone();
+        let total = self.count().expect("too many solutions to enumerate");
+
+        iter::once(current.clone()).chain((1..total).map(move |i| {
+            current ^= &self.null_space[i.trailing_zeros() as usize];
+            current.clone()
+        }))
+    }
+
+    /// Finds a solution with the fewest set bits by searching the whole null space.
+    pub fn min_weight(&self) -> BitSet {
+        let mut current = self.particular.clone();
+        let mut best = current.clone();
+        let mut best_weight = best.count_ones();
+        let total = self.count().expect("null space too large to search");
+
+        for i in 1..total {
+            current ^= &self.null_space[i.trailing_zeros() as usize];
+            let weight = current.count_ones();
+            if weight < best_weight {
+                best_weight = weight;
+                best = current.clone();
+            }
+        }
+
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bits(s: &str) -> BitSet {
+        s.chars().map(|c| c == '1').collect()
+    }
+
+    #[test]
+    fn rank() {
+        let matrix = Matrix::from_columns(3, &[bits("110"), bits("011"), bits("101")]);
+        assert_eq!(matrix.rank(), 2);
+    }
+
+    #[test]
+    fn solve_inconsistent() {
+        let matrix = Matrix::from_columns(2, &[bits("11")]);
+        assert!(matrix.solve(&bits("10")).is_none());
+    }
+
+    #[test]
+    fn solve_enumerates_all_solutions() {
+        let columns = [bits("110"), bits("011"), bits("101"), bits("100")];
+        let matrix = Matrix::from_columns(3, &columns);
+        let rhs = bits("010");
+        let solution = matrix.solve(&rhs).unwrap();
+
+        assert_eq!(solution.count(), Some(2));
+
+        let all: Vec<BitSet> = solution.solutions_iter().collect();
+        assert_eq!(all.len(), 2);
+
+        for x in all {
+            let mut result = BitSet::new(3);
+            for button in x.ones_iter() {
+                result ^= &columns[button];
+            }
+            assert_eq!(result, rhs);
+        }
+    }
+
+    #[test]
+    fn min_weight() {
+        // Lights .##. with buttons (3) (1,3) (2) (2,3) (0,2) (0,1) can be solved in 2 presses
+        let columns = [
+            bits("0001"),
+            bits("0101"),
+            bits("0010"),
+            bits("0011"),
+            bits("1010"),
+            bits("1100"),
+        ];
+        let matrix = Matrix::from_columns(4, &columns);
+        let solution = matrix.solve(&bits("0110")).unwrap();
+
+        assert_eq!(solution.min_weight().count_ones(), 2);
+    }
+}
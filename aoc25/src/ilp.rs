@@ -0,0 +1,442 @@
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+/// An exact fraction, always stored in lowest terms with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { num: 0, den: 1 };
+    pub const ONE: Rational = Rational { num: 1, den: 1 };
+
+    pub fn new(num: i128, den: i128) -> Self {
+        assert!(den != 0, "zero denominator");
+        let sign = if den < 0 { -1 } else { 1 };
+        let divisor = gcd(num, den).max(1);
+        Rational {
+            num: sign * num / divisor,
+            den: sign * den / divisor,
+        }
+    }
+
+    pub fn num(&self) -> i128 {
+        self.num
+    }
+
+    pub fn den(&self) -> i128 {
+        self.den
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.num == 0
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.den == 1
+    }
+
+    /// Returns the value as an integer if it has no fractional part.
+    pub fn to_integer(&self) -> Option<i128> {
+        self.is_integer().then_some(self.num)
+    }
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Rational {
+            num: value as i128,
+            den: 1,
+        }
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, rhs: Rational) -> Rational {
+        Rational::new(self.num * rhs.den + rhs.num * self.den, self.den * rhs.den)
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, rhs: Rational) -> Rational {
+        self + -rhs
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, rhs: Rational) -> Rational {
+        Rational::new(self.num * rhs.num, self.den * rhs.den)
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    fn div(self, rhs: Rational) -> Rational {
+        Rational::new(self.num * rhs.den, self.den * rhs.num)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.num * other.den).cmp(&(other.num * self.den))
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+/// A system `A x = b` over non-negative integers `x`, where `A` is given column by column.
+///
+/// Each column is one variable, e.g. a button and the counters it increments.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct System {
+    columns: Vec<Vec<i64>>,
+    rhs: Vec<i64>,
+    upper_bounds: Vec<Option<i64>>,
+}
+
+impl System {
+    /// Creates a system. If every coefficient is non-negative, each variable is bounded by the
+    /// smallest `b_i / a_ij` over the rows it appears in.
+    pub fn new(columns: Vec<Vec<i64>>, rhs: Vec<i64>) -> Self {
+        for (x, column) in columns.iter().enumerate() {
+            assert_eq!(column.len(), rhs.len(), "column {} has the wrong length", x);
+        }
+
+        let non_negative = columns.iter().flatten().all(|a| *a >= 0);
+        let upper_bounds = columns
+            .iter()
+            .map(|column| non_negative.then(|| Self::infer_upper_bound(column, &rhs)))
+            .collect();
+
+        System {
+            columns,
+            rhs,
+            upper_bounds,
+        }
+    }
+
+    /// Builds the columns from the indices each variable increments by one, which is the shape of
+    /// a button wired to a set of counters.
+    pub fn from_increments<I>(variables: I, rhs: Vec<i64>) -> Self
+    where
+        I: IntoIterator,
+        I::Item: IntoIterator<Item = usize>,
+    {
+        let columns = variables
+            .into_iter()
+            .map(|indices| {
+                let mut column = vec![0; rhs.len()];
+                for i in indices {
+                    column[i] += 1;
+                }
+                column
+            })
+            .collect();
+        System::new(columns, rhs)
+    }
+
+    pub fn with_upper_bound(mut self, variable: usize, bound: i64) -> Self {
+        self.upper_bounds[variable] = Some(bound);
+        self
+    }
+
+    pub fn num_variables(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn upper_bound(&self, variable: usize) -> Option<i64> {
+        self.upper_bounds[variable]
+    }
+
+    fn infer_upper_bound(column: &[i64], rhs: &[i64]) -> i64 {
+        column
+            .iter()
+            .zip(rhs)
+            .filter(|(a, _)| **a > 0)
+            .map(|(a, b)| b.div_euclid(*a))
+            .min()
+            // The variable has no effect, so any minimal solution leaves it at zero
+            .unwrap_or(0)
+    }
+
+    /// Finds a solution minimizing the sum of all variables, or `None` if there is no solution.
+    ///
+    /// Runs Gaussian elimination over the rationals and then searches every assignment of the
+    /// free variables within their bounds, so the cost grows with the product of those bounds.
+    ///
+    /// Panics if a free variable has no upper bound.
+    pub fn minimize_sum(&self) -> Option<Vec<i64>> {
+        let reduced = self.reduce()?;
+
+        for free in &reduced.free {
+            assert!(
+                self.upper_bounds[*free].is_some(),
+                "free variable {} has no upper bound",
+                free
+            );
+        }
+
+        let mut search = Search {
+            system: self,
+            reduced: &reduced,
+            assignment: vec![0; self.num_variables()],
+            best: None,
+        };
+        search.run(0, 0);
+        search.best.map(|(_, solution)| solution)
+    }
+
+    fn reduce(&self) -> Option<Reduced> {
+        let num_vars = self.num_variables();
+        let mut rows: Vec<Vec<Rational>> = (0..self.rhs.len())
+            .map(|y| {
+                self.columns
+                    .iter()
+                    .map(|column| column[y].into())
+                    .chain([self.rhs[y].into()])
+                    .collect()
+            })
+            .collect();
+
+        let mut pivots = vec![];
+
+        for col in 0..num_vars {
+            let rank = pivots.len();
+            let Some(found) = (rank..rows.len()).find(|y| !rows[*y][col].is_zero()) else {
+                continue;
+            };
+
+            rows.swap(rank, found);
+
+            let pivot = rows[rank][col];
+            for value in &mut rows[rank] {
+                *value = *value / pivot;
+            }
+
+            let pivot_row = rows[rank].clone();
+
+            for (y, row) in rows.iter_mut().enumerate() {
+                let factor = row[col];
+                if y == rank || factor.is_zero() {
+                    continue;
+                }
+                for (value, pivot_value) in row.iter_mut().zip(&pivot_row) {
+                    *value = *value - factor * *pivot_value;
+                }
+            }
+
+            pivots.push(col);
+        }
+
+        if rows[pivots.len()..]
+            .iter()
+            .any(|row| !row[num_vars].is_zero())
+        {
+            // 0 = b for some non-zero b
+            return None;
+        }
+
+        let free = (0..num_vars).filter(|x| !pivots.contains(x)).collect();
+        rows.truncate(pivots.len());
+
+        Some(Reduced { rows, pivots, free })
+    }
+}
+
+/// The system in reduced row echelon form, one row per pivot variable.
+struct Reduced {
+    rows: Vec<Vec<Rational>>,
+    pivots: Vec<usize>,
+    free: Vec<usize>,
+}
+
+struct Search<'a> {
+    system: &'a System,
+    reduced: &'a Reduced,
+    assignment: Vec<i64>,
+    best: Option<(i64, Vec<i64>)>,
+}
+
+impl Search<'_> {
+    fn run(&mut self, depth: usize, partial_sum: i64) {
+        if let Some((best_sum, _)) = &self.best
+            && partial_sum >= *best_sum
+        {
+            return;
+        }
+
+        if depth == self.reduced.free.len() {
+            self.evaluate(partial_sum);
+            return;
+        }
+
+        let variable = self.reduced.free[depth];
+        let bound = self.system.upper_bounds[variable].expect("bounded free variable");
+
+        for value in 0..=bound {
+            self.assignment[variable] = value;
+            self.run(depth + 1, partial_sum + value);
+        }
+
+        self.assignment[variable] = 0;
+    }
+
+    fn evaluate(&mut self, free_sum: i64) {
+        let num_vars = self.system.num_variables();
+        let mut sum = free_sum;
+
+        for (row, pivot) in self.reduced.rows.iter().zip(&self.reduced.pivots) {
+            let mut value = row[num_vars];
+            for free in &self.reduced.free {
+                value = value - row[*free] * self.assignment[*free].into();
+            }
+
+            let Some(value) = value.to_integer() else {
+                return;
+            };
+
+            if value < 0
+                || self.system.upper_bounds[*pivot].is_some_and(|bound| value > bound as i128)
+            {
+                return;
+            }
+
+            self.assignment[*pivot] = value as i64;
+            sum += value as i64;
+        }
+
+        if self
+            .best
+            .as_ref()
+            .is_none_or(|(best_sum, _)| sum < *best_sum)
+        {
+            self.best = Some((sum, self.assignment.clone()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rational_arithmetic() {
+        let half = Rational::new(1, 2);
+        let third = Rational::new(2, 6);
+
+        assert_eq!(third, Rational::new(1, 3));
+        assert_eq!(half + third, Rational::new(5, 6));
+        assert_eq!(half - third, Rational::new(1, 6));
+        assert_eq!(half * third, Rational::new(1, 6));
+        assert_eq!(half / third, Rational::new(3, 2));
+        assert_eq!(Rational::new(3, -6), Rational::new(-1, 2));
+        assert!(third < half);
+        assert_eq!(Rational::new(4, 2).to_integer(), Some(2));
+        assert_eq!(half.to_string(), "1/2");
+    }
+
+    #[test]
+    fn minimize_sum_counters() {
+        // (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7} takes 10 presses
+        let buttons = vec![
+            vec![3],
+            vec![1, 3],
+            vec![2],
+            vec![2, 3],
+            vec![0, 2],
+            vec![0, 1],
+        ];
+        let system = System::from_increments(buttons, vec![3, 5, 4, 7]);
+        let solution = system.minimize_sum().unwrap();
+
+        assert_eq!(solution.iter().sum::<i64>(), 10);
+        assert!(solution.iter().all(|x| *x >= 0));
+    }
+
+    #[test]
+    fn minimize_sum_satisfies_system() {
+        let buttons = vec![
+            vec![0, 1, 2, 3, 4],
+            vec![0, 3, 4],
+            vec![0, 1, 2, 4, 5],
+            vec![1, 2],
+        ];
+        let rhs = vec![10, 11, 11, 5, 10, 5];
+        let system = System::from_increments(buttons.clone(), rhs.clone());
+        let solution = system.minimize_sum().unwrap();
+
+        let mut result = vec![0; rhs.len()];
+        for (button, presses) in buttons.iter().zip(&solution) {
+            for i in button {
+                result[*i] += presses;
+            }
+        }
+
+        assert_eq!(result, rhs);
+        assert_eq!(solution.iter().sum::<i64>(), 11);
+    }
+
+    #[test]
+    fn minimize_sum_infeasible() {
+        let system = System::from_increments(vec![vec![0, 1]], vec![1, 2]);
+        assert!(system.minimize_sum().is_none());
+
+        // Only solvable with a fractional number of presses
+        let system = System::new(vec![vec![2]], vec![3]);
+        assert!(system.minimize_sum().is_none());
+    }
+
+    #[test]
+    fn explicit_upper_bound() {
+        let system = System::new(vec![vec![1, -1], vec![0, 1]], vec![2, 0]);
+        assert_eq!(system.upper_bound(0), None);
+
+        let system = system.with_upper_bound(0, 5);
+        assert_eq!(system.minimize_sum(), Some(vec![2, 2]));
+    }
+}
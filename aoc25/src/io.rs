@@ -1,9 +1,14 @@
 use std::{
     fs::File,
-    io::{self, BufReader, Read},
+    io::{self, BufRead, BufReader, Read},
     path::Path,
+    str,
 };
 
+use crate::char_grid::CharGrid;
+
+const PROGRESS_INTERVAL: u64 = 8192;
+
 pub fn clear_screen() {
     print!("\x1B[3J\x1B[H\x1B[2J");
 }
@@ -16,11 +21,14 @@ pub fn show_cursor() {
     print!("\x1B[?25h");
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ReadProgress {
     pub bytes_read: u64,
     pub total_bytes: u64,
 }
 
+/// Reads a whole file, calling `progress_fn` after each chunk. The contents are kept byte for
+/// byte, and invalid UTF-8 is an [`io::ErrorKind::InvalidData`] error.
 pub fn read_to_string_with_progress<F>(path: &Path, mut progress_fn: F) -> io::Result<String>
 where
     F: FnMut(ReadProgress),
@@ -29,7 +37,7 @@ where
     let total_bytes = file.metadata()?.len();
     let mut reader = BufReader::new(file);
 
-    let mut buffer = String::new();
+    let mut buffer = vec![];
     let mut chunk = vec![0u8; 8192]; // 8KB chunks
     let mut bytes_read = 0u64;
 
@@ -39,7 +47,7 @@ where
             break;
         }
 
-        buffer.push_str(&String::from_utf8_lossy(&chunk[..n]));
+        buffer.extend_from_slice(&chunk[..n]);
         bytes_read += n as u64;
 
         progress_fn(ReadProgress {
@@ -48,5 +56,266 @@ where
         });
     }
 
-    Ok(buffer)
+    String::from_utf8(buffer).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// Reads lines one at a time without holding the whole input in memory.
+///
+/// Lines are yielded without their line ending. Invalid UTF-8 is reported as an
+/// [`io::ErrorKind::InvalidData`] error rather than replaced.
+pub struct LineReader<'a, R> {
+    reader: R,
+    buffer: Vec<u8>,
+    line_num: usize,
+    bytes_read: u64,
+    total_bytes: u64,
+    last_reported: u64,
+    progress_fn: Option<Box<dyn FnMut(ReadProgress) + 'a>>,
+}
+
+impl LineReader<'_, BufReader<File>> {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let total_bytes = file.metadata()?.len();
+        Ok(LineReader::new(BufReader::new(file)).with_total_bytes(total_bytes))
+    }
+}
+
+impl<'b> From<&'b str> for LineReader<'_, &'b [u8]> {
+    fn from(input: &'b str) -> Self {
+        LineReader::new(input.as_bytes()).with_total_bytes(input.len() as u64)
+    }
+}
+
+impl<'a, R: BufRead> LineReader<'a, R> {
+    pub fn new(reader: R) -> Self {
+        LineReader {
+            reader,
+            buffer: vec![],
+            line_num: 0,
+            bytes_read: 0,
+            total_bytes: 0,
+            last_reported: 0,
+            progress_fn: None,
+        }
+    }
+
+    pub fn with_total_bytes(mut self, total_bytes: u64) -> Self {
+        self.total_bytes = total_bytes;
+        self
+    }
+
+    /// Calls `progress_fn` roughly every 8KB read, and once more at the end of the input.
+    pub fn on_progress<F>(mut self, progress_fn: F) -> Self
+    where
+        F: FnMut(ReadProgress) + 'a,
+    {
+        self.progress_fn = Some(Box::new(progress_fn));
+        self
+    }
+
+    /// The number of lines yielded so far.
+    pub fn line_num(&self) -> usize {
+        self.line_num
+    }
+
+    pub fn progress(&self) -> ReadProgress {
+        ReadProgress {
+            bytes_read: self.bytes_read,
+            total_bytes: self.total_bytes,
+        }
+    }
+
+    pub fn next_line(&mut self) -> Option<io::Result<&str>> {
+        self.buffer.clear();
+
+        let n = match self.reader.read_until(b'\n', &mut self.buffer) {
+            Ok(n) => n,
+            Err(err) => return Some(Err(err)),
+        };
+
+        self.bytes_read += n as u64;
+        self.report_progress(n == 0);
+
+        if n == 0 {
+            return None;
+        }
+
+        self.line_num += 1;
+
+        let mut line = self.buffer.as_slice();
+        if let Some(stripped) = line.strip_suffix(b"\n") {
+            line = stripped.strip_suffix(b"\r").unwrap_or(stripped);
+        }
+
+        let line_num = self.line_num;
+        Some(str::from_utf8(line).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid UTF-8 on line {}: {}", line_num, err),
+            )
+        }))
+    }
+
+    /// Parses each line with `parse`, yielding the records in order.
+    pub fn records<T, F>(self, parse: F) -> Records<'a, R, F>
+    where
+        F: FnMut(&str) -> T,
+    {
+        Records { lines: self, parse }
+    }
+
+    /// Builds a grid row by row from the remaining lines, stopping at the first blank line.
+    pub fn read_char_grid(&mut self) -> io::Result<CharGrid> {
+        let mut grid = CharGrid::default();
+
+        while let Some(line) = self.next_line() {
+            let line = line?;
+            if line.is_empty() {
+                break;
+            }
+            grid.push_row(line);
+        }
+
+        Ok(grid)
+    }
+
+    fn report_progress(&mut self, finished: bool) {
+        let Some(progress_fn) = self.progress_fn.as_mut() else {
+            return;
+        };
+
+        if !finished && self.bytes_read - self.last_reported < PROGRESS_INTERVAL {
+            return;
+        }
+
+        if finished && self.bytes_read == self.last_reported && self.bytes_read > 0 {
+            // Already reported
+            return;
+        }
+
+        self.last_reported = self.bytes_read;
+        progress_fn(ReadProgress {
+            bytes_read: self.bytes_read,
+            total_bytes: self.total_bytes,
+        });
+    }
+}
+
+pub struct Records<'a, R, F> {
+    lines: LineReader<'a, R>,
+    parse: F,
+}
+
+impl<R: BufRead, T, F> Iterator for Records<'_, R, F>
+where
+    F: FnMut(&str) -> T,
+{
+    type Item = io::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let parse = &mut self.parse;
+        self.lines.next_line().map(|line| line.map(&mut *parse))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_line_strips_line_endings() {
+        let mut lines = LineReader::from("abc\r\ndef\n\nghi");
+
+        assert_eq!(lines.next_line().unwrap().unwrap(), "abc");
+        assert_eq!(lines.next_line().unwrap().unwrap(), "def");
+        assert_eq!(lines.next_line().unwrap().unwrap(), "");
+        assert_eq!(lines.next_line().unwrap().unwrap(), "ghi");
+        assert!(lines.next_line().is_none());
+        assert_eq!(lines.line_num(), 4);
+    }
+
+    #[test]
+    fn next_line_invalid_utf8() {
+        let input: &[u8] = b"ok\n\xff\xfe\n";
+        let mut lines = LineReader::new(input);
+
+        assert_eq!(lines.next_line().unwrap().unwrap(), "ok");
+
+        let err = lines.next_line().unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn records() {
+        let values: Vec<u32> = LineReader::from("1\n2\n3\n")
+            .records(|line| line.parse::<u32>().unwrap())
+            .collect::<io::Result<_>>()
+            .unwrap();
+
+        assert_eq!(values, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn read_char_grid() {
+        let mut lines = LineReader::from("AB\nCD\n\nrest");
+        let grid = lines.read_char_grid().unwrap();
+
+        assert_eq!(grid, CharGrid::from("AB\nCD"));
+        assert_eq!(lines.next_line().unwrap().unwrap(), "rest");
+    }
+
+    #[test]
+    fn read_to_string_keeps_contents() {
+        let path = std::env::temp_dir().join(format!("aoc25-io-{}.txt", std::process::id()));
+        let contents = "\n\nab\r\ncd\n";
+        std::fs::write(&path, contents).unwrap();
+
+        // The callback can borrow locals
+        let mut reports = vec![];
+        let read = read_to_string_with_progress(&path, |p| reports.push(p.bytes_read));
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(read.unwrap(), contents);
+        assert_eq!(reports, vec![contents.len() as u64]);
+    }
+
+    #[test]
+    fn read_to_string_across_chunks() {
+        let path = std::env::temp_dir().join(format!("aoc25-io-chunks-{}.txt", std::process::id()));
+        // The two bytes of é straddle the end of the first 8KB chunk
+        let contents = format!("{}é\n", "a".repeat(8191));
+        std::fs::write(&path, &contents).unwrap();
+
+        let read = read_to_string_with_progress(&path, |_| {});
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(read.unwrap(), contents);
+    }
+
+    #[test]
+    fn read_to_string_invalid_utf8() {
+        let path =
+            std::env::temp_dir().join(format!("aoc25-io-invalid-{}.txt", std::process::id()));
+        std::fs::write(&path, b"ok\n\xff\xfe\n").unwrap();
+
+        let read = read_to_string_with_progress(&path, |_| {});
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(read.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn progress_reported_at_end() {
+        let mut reports = vec![];
+        let mut lines = LineReader::from("a\nb\n").on_progress(|p| reports.push(p));
+
+        while lines.next_line().is_some() {}
+        drop(lines);
+
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].bytes_read, 4);
+        assert_eq!(reports[0].total_bytes, 4);
+    }
 }
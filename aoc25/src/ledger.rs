@@ -0,0 +1,363 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use clap::ValueEnum;
+
+const HEADER: &str = "# day\tpart\tanswer\tverdict";
+const COMMENT: char = '#';
+const SEPARATOR: char = '\t';
+
+/// What the puzzle site said about an answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, ValueEnum)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint about which way
+    Wrong,
+}
+
+impl Verdict {
+    pub fn is_correct(self) -> bool {
+        self == Verdict::Correct
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Wrong => "wrong",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too-high" => Ok(Verdict::TooHigh),
+            "too-low" => Ok(Verdict::TooLow),
+            "wrong" => Ok(Verdict::Wrong),
+            _ => Err(format!("unknown verdict: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Entry {
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// The result of checking a candidate answer against what is already known.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Check {
+    /// Nothing rules the answer out
+    Unknown,
+    /// The answer was submitted before
+    Known(Verdict),
+    /// The part is solved with a different answer
+    Solved { correct: String },
+    /// The answer is at or beyond a bound learned from too-high or too-low verdicts
+    OutOfBounds {
+        above: Option<i128>,
+        below: Option<i128>,
+    },
+}
+
+impl Check {
+    /// Whether the answer is worth submitting. Answers outside the learned bounds still are, as
+    /// the bounds only make them unlikely.
+    pub fn is_submittable(&self) -> bool {
+        matches!(self, Check::Unknown | Check::OutOfBounds { .. })
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Check::Unknown => write!(f, "not tried yet"),
+            Check::Known(verdict) => write!(f, "already submitted, it was {}", verdict),
+            Check::Solved { correct } => write!(f, "already solved with {}", correct),
+            Check::OutOfBounds { above, below } => {
+                write!(f, "outside the known bounds: the answer is")?;
+                if let Some(above) = above {
+                    write!(f, " above {}", above)?;
+                }
+                if above.is_some() && below.is_some() {
+                    write!(f, " and")?;
+                }
+                if let Some(below) = below {
+                    write!(f, " below {}", below)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A local record of submitted answers and their verdicts, one tab separated entry per line.
+///
+/// Entries are only ever appended, so the file doubles as a history. When an answer is recorded
+/// more than once, the latest verdict wins.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Ledger {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl Ledger {
+    /// Loads the ledger at `path`, or starts an empty one if the file does not exist yet.
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err),
+        };
+
+        let entries = contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with(COMMENT))
+            .map(|(i, line)| {
+                parse_entry(line).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("invalid ledger entry on line {}", i + 1),
+                    )
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Ledger { path, entries })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// All entries for a day and part, oldest first.
+    pub fn entries(&self, day: u32, part: u32) -> impl Iterator<Item = &Entry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.day == day && entry.part == part)
+    }
+
+    /// The latest verdict for an answer, if it was submitted before.
+    pub fn verdict(&self, day: u32, part: u32, answer: impl Display) -> Option<Verdict> {
+        let answer = answer.to_string();
+        self.entries(day, part)
+            .filter(|entry| entry.answer == answer.trim())
+            .last()
+            .map(|entry| entry.verdict)
+    }
+
+    pub fn correct_answer(&self, day: u32, part: u32) -> Option<&str> {
+        self.entries(day, part)
+            .filter(|entry| entry.verdict.is_correct())
+            .last()
+            .map(|entry| entry.answer.as_str())
+    }
+
+    /// The exclusive range a numeric answer must fall in, learned from too-low and too-high
+    /// verdicts.
+    pub fn bounds(&self, day: u32, part: u32) -> (Option<i128>, Option<i128>) {
+        let numeric = |verdict| {
+            self.entries(day, part)
+                .filter(move |entry| entry.verdict == verdict)
+                .filter_map(|entry| entry.answer.parse::<i128>().ok())
+        };
+        (
+            numeric(Verdict::TooLow).max(),
+            numeric(Verdict::TooHigh).min(),
+        )
+    }
+
+    pub fn check(&self, day: u32, part: u32, answer: impl Display) -> Check {
+        let answer = answer.to_string();
+        let answer = answer.trim();
+
+        if let Some(verdict) = self.verdict(day, part, answer) {
+            return Check::Known(verdict);
+        }
+        if let Some(correct) = self.correct_answer(day, part) {
+            return Check::Solved {
+                correct: correct.to_string(),
+            };
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Check::Unknown;
+        };
+        let (above, below) = self.bounds(day, part);
+        if above.is_some_and(|above| value <= above) || below.is_some_and(|below| value >= below) {
+            return Check::OutOfBounds { above, below };
+        }
+
+        Check::Unknown
+    }
+
+    /// Records a verdict and appends it to the file.
+    pub fn record(
+        &mut self,
+        day: u32,
+        part: u32,
+        answer: impl Display,
+        verdict: Verdict,
+    ) -> io::Result<()> {
+        let answer = answer.to_string().trim().to_string();
+        if answer.is_empty() || answer.contains(['\t', '\n']) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid answer: {:?}", answer),
+            ));
+        }
+
+        let is_new_file = !self.path.exists();
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        if is_new_file {
+            writeln!(file, "{}", HEADER)?;
+        }
+
+        let entry = Entry {
+            day,
+            part,
+            answer,
+            verdict,
+        };
+        writeln!(
+            file,
+            "{}{sep}{}{sep}{}{sep}{}",
+            entry.day,
+            entry.part,
+            entry.answer,
+            entry.verdict,
+            sep = SEPARATOR
+        )?;
+        self.entries.push(entry);
+
+        Ok(())
+    }
+}
+
+fn parse_entry(line: &str) -> Option<Entry> {
+    let mut fields = line.split(SEPARATOR);
+    let entry = Entry {
+        day: fields.next()?.parse().ok()?,
+        part: fields.next()?.parse().ok()?,
+        answer: fields.next()?.to_string(),
+        verdict: fields.next()?.parse().ok()?,
+    };
+    fields.next().is_none().then_some(entry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_ledger(name: &str) -> Ledger {
+        let path =
+            std::env::temp_dir().join(format!("aoc25-ledger-{}-{}.tsv", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        Ledger::open(path).unwrap()
+    }
+
+    #[test]
+    fn record_and_reload() {
+        let mut ledger = temp_ledger("reload");
+        ledger.record(1, 1, 42, Verdict::TooLow).unwrap();
+        ledger.record(1, 1, "100 ", Verdict::Correct).unwrap();
+
+        let reloaded = Ledger::open(ledger.path()).unwrap();
+        assert_eq!(reloaded, ledger);
+        assert_eq!(reloaded.entries(1, 1).count(), 2);
+        assert_eq!(reloaded.entries(1, 2).count(), 0);
+        assert_eq!(reloaded.correct_answer(1, 1), Some("100"));
+        assert!(
+            fs::read_to_string(ledger.path())
+                .unwrap()
+                .starts_with(HEADER)
+        );
+
+        fs::remove_file(ledger.path()).unwrap();
+    }
+
+    #[test]
+    fn refuses_known_answers() {
+        let mut ledger = temp_ledger("known");
+        ledger.record(3, 2, 500, Verdict::Wrong).unwrap();
+
+        assert_eq!(ledger.check(3, 2, 500), Check::Known(Verdict::Wrong));
+        assert!(!ledger.check(3, 2, 500).is_submittable());
+        assert!(ledger.check(3, 2, 501).is_submittable());
+        assert!(ledger.check(3, 1, 500).is_submittable());
+
+        ledger.record(3, 2, 777, Verdict::Correct).unwrap();
+        assert_eq!(
+            ledger.check(3, 2, 501),
+            Check::Solved {
+                correct: "777".to_string()
+            }
+        );
+
+        fs::remove_file(ledger.path()).unwrap();
+    }
+
+    #[test]
+    fn learned_bounds() {
+        let mut ledger = temp_ledger("bounds");
+        ledger.record(5, 1, 100, Verdict::TooLow).unwrap();
+        ledger.record(5, 1, 150, Verdict::TooLow).unwrap();
+        ledger.record(5, 1, 300, Verdict::TooHigh).unwrap();
+
+        assert_eq!(ledger.bounds(5, 1), (Some(150), Some(300)));
+        assert_eq!(ledger.check(5, 1, 200), Check::Unknown);
+        assert_eq!(
+            ledger.check(5, 1, 120),
+            Check::OutOfBounds {
+                above: Some(150),
+                below: Some(300)
+            }
+        );
+        assert!(ledger.check(5, 1, 301).is_submittable());
+        assert_eq!(
+            ledger.check(5, 1, 120).to_string(),
+            "outside the known bounds: the answer is above 150 and below 300"
+        );
+        assert_eq!(ledger.check(5, 1, "abc"), Check::Unknown);
+
+        fs::remove_file(ledger.path()).unwrap();
+    }
+
+    #[test]
+    fn invalid_files() {
+        let ledger = temp_ledger("invalid");
+        fs::write(ledger.path(), "# comment\n1\t1\t5\tmaybe\n").unwrap();
+
+        let error = Ledger::open(ledger.path()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().contains("line 2"));
+
+        fs::remove_file(ledger.path()).unwrap();
+    }
+}
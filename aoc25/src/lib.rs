@@ -1,13 +1,21 @@
-#![feature(
-    trait_alias,
-    coroutines,
-    coroutine_trait,
-    iter_from_coroutine,
-    stmt_expr_attributes
-)]
+#![feature(trait_alias, coroutines, iter_from_coroutine)]
 
+pub mod bitset;
+pub mod cache;
 pub mod char_grid;
+pub mod debugger;
+pub mod dial;
+pub mod digit_dp;
+pub mod direction;
+pub mod expr;
+pub mod fetch;
+pub mod gf2;
+pub mod ilp;
 pub mod io;
+pub mod ledger;
 pub mod math;
+pub mod parse;
 pub mod range;
+pub mod scaffold;
+pub mod subsequence;
 pub mod util;
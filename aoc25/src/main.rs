@@ -0,0 +1,155 @@
+use std::{env, io, path::Path, process};
+
+use aoc25::{
+    fetch::{self, Fetcher},
+    ledger::{Check, Ledger, Verdict},
+    scaffold::{self, Puzzle, Template},
+};
+use clap::{Parser, Subcommand};
+
+#[derive(Parser, Debug)]
+#[command(version, author)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Creates a puzzle crate and registers it in the workspace
+    New {
+        day: u32,
+        part: u32,
+        /// Defaults to the name of part 1 when copying it
+        name: Option<String>,
+        #[arg(short, long, value_enum, default_value_t)]
+        template: Template,
+        /// Start from a copy of part 1 instead of a template
+        #[arg(short, long)]
+        copy: bool,
+    },
+    /// Downloads a day's input and first example into its puzzle crates' empty input files
+    Fetch { day: u32 },
+    /// Checks an answer against the ledger, or records the verdict it got
+    Answer {
+        day: u32,
+        part: u32,
+        answer: String,
+        #[arg(short, long, value_enum)]
+        verdict: Option<Verdict>,
+    },
+}
+
+const FETCH_CACHE_DIR: &str = ".cache/aoc";
+const INPUT_FILE: &str = "input.txt";
+const EXAMPLE_FILE: &str = "input_example.txt";
+const LEDGER_FILE: &str = "answers.tsv";
+
+fn main() {
+    let args = Args::parse();
+
+    let result = match args.command {
+        Command::New {
+            day,
+            part,
+            name,
+            template,
+            copy,
+        } => new_puzzle(day, part, name, template, copy),
+        Command::Fetch { day } => fetch_inputs(day),
+        Command::Answer {
+            day,
+            part,
+            answer,
+            verdict,
+        } => check_answer(day, part, &answer, verdict),
+    };
+
+    if let Err(error) = result {
+        eprintln!("Error: {}", error);
+        process::exit(1);
+    }
+}
+
+fn new_puzzle(
+    day: u32,
+    part: u32,
+    name: Option<String>,
+    template: Template,
+    copy: bool,
+) -> io::Result<()> {
+    let root = scaffold::find_workspace_root(&env::current_dir()?)?;
+
+    let dir = if copy {
+        let part_one = scaffold::find_puzzle(&root, day, 1)?.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("day {} has no part 1", day),
+            )
+        })?;
+        let name = name.unwrap_or_else(|| part_one.name.clone());
+        scaffold::copy(&root, &part_one, &Puzzle::new(day, part, &name)?)?
+    } else {
+        let name =
+            name.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "a name is required"))?;
+        scaffold::create(&root, &Puzzle::new(day, part, &name)?, template)?
+    };
+
+    println!("Created {}", dir.display());
+    Ok(())
+}
+
+fn fetch_inputs(day: u32) -> io::Result<()> {
+    let root = scaffold::find_workspace_root(&env::current_dir()?)?;
+    let fetcher = Fetcher::from_env(root.join(FETCH_CACHE_DIR));
+    let input = fetcher.input(day)?;
+    let examples = fetcher.examples(day)?;
+
+    for part in 1..=2 {
+        let Some(puzzle) = scaffold::find_puzzle(&root, day, part)? else {
+            continue;
+        };
+        let dir = root.join(puzzle.crate_name());
+
+        write_input(&dir.join(INPUT_FILE), &input)?;
+        if let Some(example) = examples.first() {
+            write_input(&dir.join(EXAMPLE_FILE), example)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_input(path: &Path, contents: &str) -> io::Result<()> {
+    if fetch::write_if_empty(path, contents)? {
+        println!("Wrote {}", path.display());
+    } else {
+        println!("Kept {}, it is not empty", path.display());
+    }
+    Ok(())
+}
+
+fn check_answer(day: u32, part: u32, answer: &str, verdict: Option<Verdict>) -> io::Result<()> {
+    let root = scaffold::find_workspace_root(&env::current_dir()?)?;
+    let mut ledger = Ledger::open(root.join(LEDGER_FILE))?;
+
+    if let Some(verdict) = verdict {
+        ledger.record(day, part, answer, verdict)?;
+        println!("Recorded {} as {}", answer, verdict);
+        return Ok(());
+    }
+
+    let check = ledger.check(day, part, answer);
+    if !check.is_submittable() {
+        return Err(io::Error::other(format!(
+            "do not submit {}: {}",
+            answer, check
+        )));
+    }
+    if let Check::OutOfBounds { .. } = check {
+        eprintln!("Warning: {} is {}", answer, check);
+    } else {
+        println!("{} is {}", answer, check);
+    }
+    Ok(())
+}
@@ -0,0 +1,432 @@
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+const LIMB_BITS: u32 = u32::BITS;
+/// Largest power of 10 that fits in a limb, used to print 9 digits at a time
+const DECIMAL_CHUNK: u32 = 1_000_000_000;
+const DECIMAL_CHUNK_DIGITS: usize = 9;
+
+/// An arbitrary-precision signed integer.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    /// Magnitude in base 2^32, least significant limb first, without trailing zero limbs
+    limbs: Vec<u32>,
+}
+
+impl BigInt {
+    pub const ZERO: BigInt = BigInt {
+        negative: false,
+        limbs: vec![],
+    };
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn abs(&self) -> BigInt {
+        BigInt {
+            negative: false,
+            limbs: self.limbs.clone(),
+        }
+    }
+
+    fn from_parts(negative: bool, mut limbs: Vec<u32>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        BigInt {
+            negative: negative && !limbs.is_empty(),
+            limbs,
+        }
+    }
+
+    fn from_magnitude(negative: bool, mut magnitude: u128) -> Self {
+        let mut limbs = vec![];
+        while magnitude > 0 {
+            limbs.push(magnitude as u32);
+            magnitude >>= LIMB_BITS;
+        }
+        BigInt::from_parts(negative, limbs)
+    }
+
+    /// The magnitude if it fits in 128 bits.
+    fn magnitude_u128(&self) -> Option<u128> {
+        if self.limbs.len() > (u128::BITS / LIMB_BITS) as usize {
+            return None;
+        }
+        Some(
+            self.limbs
+                .iter()
+                .rev()
+                .fold(0u128, |acc, limb| acc << LIMB_BITS | *limb as u128),
+        )
+    }
+}
+
+fn cmp_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut result = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0u64;
+
+    for (i, limb) in long.iter().enumerate() {
+        let sum = *limb as u64 + *short.get(i).unwrap_or(&0) as u64 + carry;
+        result.push(sum as u32);
+        carry = sum >> LIMB_BITS;
+    }
+    if carry > 0 {
+        result.push(carry as u32);
+    }
+
+    result
+}
+
+/// Computes `a - b`, where `a` must not be smaller than `b`.
+fn sub_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+
+    for (i, limb) in a.iter().enumerate() {
+        let mut diff = *limb as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        borrow = (diff < 0) as i64;
+        if diff < 0 {
+            diff += 1 << LIMB_BITS;
+        }
+        result.push(diff as u32);
+    }
+
+    result
+}
+
+fn mul_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = vec![0u32; a.len() + b.len()];
+
+    for (i, x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, y) in b.iter().enumerate() {
+            let product = *x as u64 * *y as u64 + result[i + j] as u64 + carry;
+            result[i + j] = product as u32;
+            carry = product >> LIMB_BITS;
+        }
+        result[i + b.len()] = carry as u32;
+    }
+
+    result
+}
+
+/// Divides in place by a single limb and returns the remainder.
+fn div_rem_limb(limbs: &mut Vec<u32>, divisor: u32) -> u32 {
+    let mut remainder = 0u64;
+
+    for limb in limbs.iter_mut().rev() {
+        let current = remainder << LIMB_BITS | *limb as u64;
+        *limb = (current / divisor as u64) as u32;
+        remainder = current % divisor as u64;
+    }
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+
+    remainder as u32
+}
+
+impl Add<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, rhs: &BigInt) -> BigInt {
+        if self.negative == rhs.negative {
+            return BigInt::from_parts(self.negative, add_magnitude(&self.limbs, &rhs.limbs));
+        }
+
+        match cmp_magnitude(&self.limbs, &rhs.limbs) {
+            Ordering::Equal => BigInt::ZERO,
+            Ordering::Greater => {
+                BigInt::from_parts(self.negative, sub_magnitude(&self.limbs, &rhs.limbs))
+            }
+            Ordering::Less => {
+                BigInt::from_parts(rhs.negative, sub_magnitude(&rhs.limbs, &self.limbs))
+            }
+        }
+    }
+}
+
+impl Sub<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, rhs: &BigInt) -> BigInt {
+        self + &-rhs
+    }
+}
+
+impl Mul<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, rhs: &BigInt) -> BigInt {
+        BigInt::from_parts(
+            self.negative != rhs.negative,
+            mul_magnitude(&self.limbs, &rhs.limbs),
+        )
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.limbs.clone())
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.limbs)
+    }
+}
+
+macro_rules! forward_ops {
+    ($($op:ident, $op_fn:ident, $assign:ident, $assign_fn:ident);*) => {
+        $(
+            impl $op for BigInt {
+                type Output = BigInt;
+
+                fn $op_fn(self, rhs: BigInt) -> BigInt {
+                    (&self).$op_fn(&rhs)
+                }
+            }
+
+            impl $op<&BigInt> for BigInt {
+                type Output = BigInt;
+
+                fn $op_fn(self, rhs: &BigInt) -> BigInt {
+                    (&self).$op_fn(rhs)
+                }
+            }
+
+            impl $assign<&BigInt> for BigInt {
+                fn $assign_fn(&mut self, rhs: &BigInt) {
+                    *self = (&*self).$op_fn(rhs);
+                }
+            }
+
+            impl $assign for BigInt {
+                fn $assign_fn(&mut self, rhs: BigInt) {
+                    *self = (&*self).$op_fn(&rhs);
+                }
+            }
+        )*
+    };
+}
+
+forward_ops!(
+    Add, add, AddAssign, add_assign;
+    Sub, sub, SubAssign, sub_assign;
+    Mul, mul, MulAssign, mul_assign
+);
+
+impl Sum for BigInt {
+    fn sum<I: Iterator<Item = BigInt>>(iter: I) -> BigInt {
+        iter.fold(BigInt::ZERO, |acc, value| acc + value)
+    }
+}
+
+impl Product for BigInt {
+    fn product<I: Iterator<Item = BigInt>>(iter: I) -> BigInt {
+        iter.fold(BigInt::from(1), |acc, value| acc * value)
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_magnitude(&self.limbs, &other.limbs),
+            (true, true) => cmp_magnitude(&other.limbs, &self.limbs),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+macro_rules! impl_from {
+    (unsigned: $($t:ty),*) => {
+        $(
+            impl From<$t> for BigInt {
+                fn from(value: $t) -> Self {
+                    BigInt::from_magnitude(false, value as u128)
+                }
+            }
+        )*
+    };
+    (signed: $($t:ty),*) => {
+        $(
+            impl From<$t> for BigInt {
+                fn from(value: $t) -> Self {
+                    BigInt::from_magnitude(value < 0, value.unsigned_abs() as u128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(unsigned: u8, u16, u32, u64, u128, usize);
+impl_from!(signed: i8, i16, i32, i64, i128, isize);
+
+impl TryFrom<&BigInt> for i128 {
+    type Error = ();
+
+    fn try_from(value: &BigInt) -> Result<Self, Self::Error> {
+        let magnitude = value.magnitude_u128().ok_or(())?;
+        if value.negative {
+            0i128.checked_sub_unsigned(magnitude).ok_or(())
+        } else {
+            i128::try_from(magnitude).map_err(|_| ())
+        }
+    }
+}
+
+impl TryFrom<&BigInt> for u128 {
+    type Error = ();
+
+    fn try_from(value: &BigInt) -> Result<Self, Self::Error> {
+        if value.negative {
+            return Err(());
+        }
+        value.magnitude_u128().ok_or(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseBigIntError;
+
+impl Display for ParseBigIntError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid integer")
+    }
+}
+
+impl std::error::Error for ParseBigIntError {}
+
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        if digits.is_empty() {
+            return Err(ParseBigIntError);
+        }
+
+        let ten = BigInt::from(10);
+        let mut result = BigInt::ZERO;
+        for ch in digits.chars() {
+            let digit = ch.to_digit(10).ok_or(ParseBigIntError)?;
+            result = &result * &ten + BigInt::from(digit);
+        }
+
+        Ok(if negative { -result } else { result })
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+
+        let mut limbs = self.limbs.clone();
+        let mut chunks = vec![];
+        while !limbs.is_empty() {
+            chunks.push(div_rem_limb(&mut limbs, DECIMAL_CHUNK));
+        }
+
+        if self.negative {
+            write!(f, "-")?;
+        }
+        let mut chunks = chunks.iter().rev();
+        write!(f, "{}", chunks.next().expect("non-zero"))?;
+        for chunk in chunks {
+            write!(f, "{:0width$}", chunk, width = DECIMAL_CHUNK_DIGITS)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(s: &str) -> BigInt {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn display_round_trip() {
+        for s in [
+            "0",
+            "-1",
+            "1000000000",
+            "340282366920938463463374607431768211456",
+            "-123456789012345678901234567890",
+        ] {
+            assert_eq!(big(s).to_string(), s);
+        }
+        assert_eq!(BigInt::from(i128::MIN).to_string(), i128::MIN.to_string());
+        assert!("12a".parse::<BigInt>().is_err());
+        assert!("-".parse::<BigInt>().is_err());
+    }
+
+    #[test]
+    fn add_and_sub() {
+        assert_eq!(
+            BigInt::from(u128::MAX) + BigInt::from(1),
+            big("340282366920938463463374607431768211456")
+        );
+        assert_eq!(BigInt::from(5) + BigInt::from(-8), BigInt::from(-3));
+        assert_eq!(BigInt::from(-5) + BigInt::from(8), BigInt::from(3));
+        assert_eq!(BigInt::from(7) - BigInt::from(7), BigInt::ZERO);
+        assert!(!(BigInt::from(7) - BigInt::from(7)).is_negative());
+    }
+
+    #[test]
+    fn mul() {
+        let factorial: BigInt = (1..=30).map(BigInt::from).product();
+        assert_eq!(factorial.to_string(), "265252859812191058636308480000000");
+        assert_eq!(BigInt::from(-3) * BigInt::from(4), BigInt::from(-12));
+        assert_eq!(BigInt::from(-3) * BigInt::ZERO, BigInt::ZERO);
+    }
+
+    #[test]
+    fn ordering_and_conversion() {
+        assert!(BigInt::from(-10) < BigInt::from(-2));
+        assert!(BigInt::from(u128::MAX) > BigInt::from(i128::MAX));
+        assert_eq!(i128::try_from(&BigInt::from(i128::MIN)), Ok(i128::MIN));
+        assert_eq!(i128::try_from(&BigInt::from(u128::MAX)), Err(()));
+        assert_eq!(u128::try_from(&BigInt::from(u128::MAX)), Ok(u128::MAX));
+        assert_eq!(u128::try_from(&BigInt::from(-1)), Err(()));
+    }
+}
@@ -0,0 +1,319 @@
+/// Digit manipulation on primitive integers without allocating.
+///
+/// Signed values are treated by magnitude, so `-123` has the digits `1, 2, 3`. Operations that
+/// build a new value keep the sign of `self`.
+pub trait Digits: Sized + Copy {
+    /// The number of base 10 digits. Zero has one digit.
+    fn digit_count(self) -> u32 {
+        self.digit_count_radix(10)
+    }
+
+    /// Panics unless `radix` is between 2 and 36.
+    fn digit_count_radix(self, radix: u32) -> u32;
+
+    /// Iterates over the base 10 digits, most significant first.
+    fn digits(self) -> DigitsIter {
+        self.digits_radix(10)
+    }
+
+    /// Iterates over the digits in `radix`, most significant first. Call `rev` for least
+    /// significant first.
+    fn digits_radix(self, radix: u32) -> DigitsIter;
+
+    /// Builds a non-negative value from base 10 digits, most significant first. Returns `None`
+    /// if a digit is out of range or the value overflows.
+    fn from_digits<I>(digits: I) -> Option<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<u32>,
+    {
+        Self::from_digits_radix(digits, 10)
+    }
+
+    fn from_digits_radix<I>(digits: I, radix: u32) -> Option<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<u32>;
+
+    /// Appends the digits of `other`, e.g. `12.concat(345) == Some(12345)`. The result takes the
+    /// sign of `self`, and a negative `other` has no digits to append, so gives `None`.
+    fn concat(self, other: Self) -> Option<Self>;
+
+    /// Reverses the base 10 digits, dropping any leading zeros this creates.
+    fn reverse_digits(self) -> Option<Self>;
+
+    /// The length of the shortest block of base 10 digits that repeats to form the number, e.g. 2
+    /// for `121212`. Returns the digit count if the number does not repeat.
+    fn period(self) -> u32;
+
+    /// How many times the shortest repeating block occurs, e.g. 3 for `121212`.
+    fn repeats(self) -> u32 {
+        self.digit_count() / self.period()
+    }
+}
+
+/// Iterator over the digits of a number, in either direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DigitsIter {
+    value: u128,
+    radix: u128,
+    /// Place value of the most significant remaining digit
+    high: u128,
+    len: u32,
+}
+
+impl DigitsIter {
+    fn new(value: u128, radix: u32) -> Self {
+        check_radix(radix);
+        let radix = radix as u128;
+        let len = magnitude_digit_count(value, radix);
+        DigitsIter {
+            value,
+            radix,
+            high: radix.pow(len - 1),
+            len,
+        }
+    }
+}
+
+impl Iterator for DigitsIter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let digit = self.value / self.high;
+        self.value %= self.high;
+        self.high /= self.radix;
+        self.len -= 1;
+        Some(digit as u32)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len as usize, Some(self.len as usize))
+    }
+}
+
+impl DoubleEndedIterator for DigitsIter {
+    fn next_back(&mut self) -> Option<u32> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let digit = self.value % self.radix;
+        self.value /= self.radix;
+        self.high /= self.radix;
+        self.len -= 1;
+        Some(digit as u32)
+    }
+}
+
+impl ExactSizeIterator for DigitsIter {}
+
+fn check_radix(radix: u32) {
+    assert!((2..=36).contains(&radix), "invalid radix: {}", radix);
+}
+
+fn magnitude_digit_count(mut value: u128, radix: u128) -> u32 {
+    let mut count = 1;
+    while value >= radix {
+        value /= radix;
+        count += 1;
+    }
+    count
+}
+
+fn from_magnitude_digits<I>(digits: I, radix: u32) -> Option<u128>
+where
+    I: IntoIterator,
+    I::Item: Into<u32>,
+{
+    digits.into_iter().try_fold(0u128, |acc, digit| {
+        let digit = digit.into();
+        if digit >= radix {
+            return None;
+        }
+        acc.checked_mul(radix as u128)?.checked_add(digit as u128)
+    })
+}
+
+fn magnitude_period(value: u128) -> u32 {
+    let len = magnitude_digit_count(value, 10);
+
+    for period in (1..len).filter(|period| len.is_multiple_of(*period)) {
+        let block = value / 10u128.pow(len - period);
+        let shift = 10u128.pow(period);
+        // A block repeated past the value's length can overflow, and then it cannot be the value
+        let repeated =
+            (1..len / period).try_fold(block, |acc, _| acc.checked_mul(shift)?.checked_add(block));
+        if repeated == Some(value) {
+            return period;
+        }
+    }
+
+    len
+}
+
+macro_rules! impl_digits {
+    ($kind:ident: $($t:ty),*) => {
+        $(
+            impl Digits for $t {
+                fn digit_count_radix(self, radix: u32) -> u32 {
+                    check_radix(radix);
+                    magnitude_digit_count(self.abs_diff(0) as u128, radix as u128)
+                }
+
+                fn digits_radix(self, radix: u32) -> DigitsIter {
+                    DigitsIter::new(self.abs_diff(0) as u128, radix)
+                }
+
+                fn from_digits_radix<I>(digits: I, radix: u32) -> Option<Self>
+                where
+                    I: IntoIterator,
+                    I::Item: Into<u32>,
+                {
+                    from_magnitude_digits(digits, radix)?.try_into().ok()
+                }
+
+                fn concat(self, other: Self) -> Option<Self> {
+                    if impl_digits!(@is_negative $kind, other) {
+                        return None;
+                    }
+                    let shift = (10 as $t).checked_pow(other.digit_count())?;
+                    let tail = <$t>::try_from(other.abs_diff(0)).ok()?;
+                    let tail = impl_digits!(@with_sign $kind, self, tail)?;
+                    self.checked_mul(shift)?.checked_add(tail)
+                }
+
+                fn reverse_digits(self) -> Option<Self> {
+                    let magnitude = from_magnitude_digits(self.digits().rev(), 10)?;
+                    let reversed = <$t>::try_from(magnitude).ok()?;
+                    impl_digits!(@with_sign $kind, self, reversed)
+                }
+
+                fn period(self) -> u32 {
+                    magnitude_period(self.abs_diff(0) as u128)
+                }
+            }
+        )*
+    };
+
+    // Gives a non-negative magnitude the sign of another value
+    (@with_sign signed, $of:expr, $magnitude:expr) => {
+        if $of < 0 {
+            $magnitude.checked_neg()
+        } else {
+            Some($magnitude)
+        }
+    };
+    (@with_sign unsigned, $of:expr, $magnitude:expr) => {
+        Some($magnitude)
+    };
+
+    (@is_negative signed, $value:expr) => {
+        $value < 0
+    };
+    (@is_negative unsigned, $value:expr) => {
+        false
+    };
+}
+
+impl_digits!(unsigned: u8, u16, u32, u64, u128, usize);
+impl_digits!(signed: i8, i16, i32, i64, i128, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digit_count() {
+        assert_eq!(0u8.digit_count(), 1);
+        assert_eq!(9u32.digit_count(), 1);
+        assert_eq!(10u32.digit_count(), 2);
+        assert_eq!(u64::MAX.digit_count(), 20);
+        assert_eq!(u128::MAX.digit_count(), 39);
+        assert_eq!(i8::MIN.digit_count(), 3);
+        assert_eq!(255u8.digit_count_radix(2), 8);
+    }
+
+    #[test]
+    fn digits_both_directions() {
+        assert_eq!(1203u32.digits().collect::<Vec<_>>(), vec![1, 2, 0, 3]);
+        assert_eq!(1203u32.digits().rev().collect::<Vec<_>>(), vec![3, 0, 2, 1]);
+        assert_eq!((-45i64).digits().collect::<Vec<_>>(), vec![4, 5]);
+        assert_eq!(0u64.digits().collect::<Vec<_>>(), vec![0]);
+        assert_eq!(0xa5u8.digits_radix(16).collect::<Vec<_>>(), vec![10, 5]);
+
+        let mut digits = 12345u32.digits();
+        assert_eq!(digits.next(), Some(1));
+        assert_eq!(digits.next_back(), Some(5));
+        assert_eq!(digits.len(), 3);
+        assert_eq!(digits.collect::<Vec<_>>(), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn from_digits() {
+        assert_eq!(u64::from_digits([9u32, 8, 7]), Some(987));
+        assert_eq!(u8::from_digits([2u8, 5, 6]), None);
+        assert_eq!(i8::from_digits([1u8, 2, 7]), Some(127));
+        assert_eq!(u32::from_digits([1u32, 10]), None);
+        assert_eq!(u32::from_digits_radix([1u32, 0, 1], 2), Some(5));
+        assert_eq!(u32::from_digits(Vec::<u32>::new()), Some(0));
+    }
+
+    #[test]
+    fn base_conversion_round_trip() {
+        for value in [0u64, 1, 255, 123_456_789, u64::MAX] {
+            for radix in [2, 7, 10, 16, 36] {
+                let digits = value.digits_radix(radix);
+                assert_eq!(u64::from_digits_radix(digits, radix), Some(value));
+            }
+        }
+    }
+
+    #[test]
+    fn concat() {
+        assert_eq!(12u32.concat(345), Some(12345));
+        assert_eq!(12u32.concat(0), Some(120));
+        assert_eq!((-12i32).concat(34), Some(-1234));
+        assert_eq!(25u8.concat(5), Some(255));
+        assert_eq!(26u8.concat(5), None);
+        assert_eq!(12i32.concat(-3), None);
+        assert_eq!((-12i32).concat(-3), None);
+    }
+
+    #[test]
+    fn reverse_digits() {
+        assert_eq!(1230u32.reverse_digits(), Some(321));
+        assert_eq!((-123i16).reverse_digits(), Some(-321));
+        assert_eq!(199u8.reverse_digits(), None);
+    }
+
+    #[test]
+    fn period() {
+        assert_eq!(121212u64.period(), 2);
+        assert_eq!(121212u64.repeats(), 3);
+        assert_eq!(1111u32.period(), 1);
+        assert_eq!(1188511885u64.period(), 5);
+        assert_eq!(123u32.period(), 3);
+        assert_eq!(123u32.repeats(), 1);
+        assert_eq!((-77i8).repeats(), 2);
+        // Repeating a short block of a 39 digit value overflows u128 before it can match
+        assert_eq!(u128::MAX.period(), 39);
+        assert_eq!(i128::MIN.period(), 39);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid radix")]
+    fn digit_count_radix_one() {
+        5u32.digit_count_radix(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid radix")]
+    fn digit_count_radix_zero() {
+        5u32.digit_count_radix(0);
+    }
+}
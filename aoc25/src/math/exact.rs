@@ -0,0 +1,214 @@
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign},
+};
+
+use crate::math::big_int::BigInt;
+
+/// An integer that stays a plain `i128` while it fits and switches to a [`BigInt`] on overflow,
+/// so answers are exact instead of silently wrapping.
+///
+/// A value is only ever `Big` when it does not fit in an `i128`, so equal values compare equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Exact {
+    Small(i128),
+    Big(BigInt),
+}
+
+impl Exact {
+    pub const ZERO: Exact = Exact::Small(0);
+    pub const ONE: Exact = Exact::Small(1);
+
+    /// Whether the value has outgrown an `i128`.
+    pub fn is_big(&self) -> bool {
+        matches!(self, Exact::Big(_))
+    }
+
+    pub fn to_i128(&self) -> Option<i128> {
+        match self {
+            Exact::Small(value) => Some(*value),
+            Exact::Big(_) => None,
+        }
+    }
+
+    pub fn to_big_int(&self) -> BigInt {
+        match self {
+            Exact::Small(value) => BigInt::from(*value),
+            Exact::Big(value) => value.clone(),
+        }
+    }
+
+    fn from_big_int(value: BigInt) -> Self {
+        match i128::try_from(&value) {
+            Ok(small) => Exact::Small(small),
+            Err(()) => Exact::Big(value),
+        }
+    }
+
+    fn apply(
+        &self,
+        rhs: &Exact,
+        checked: fn(i128, i128) -> Option<i128>,
+        big: fn(&BigInt, &BigInt) -> BigInt,
+    ) -> Exact {
+        if let (Exact::Small(a), Exact::Small(b)) = (self, rhs)
+            && let Some(result) = checked(*a, *b)
+        {
+            return Exact::Small(result);
+        }
+        Exact::from_big_int(big(&self.to_big_int(), &rhs.to_big_int()))
+    }
+}
+
+impl Default for Exact {
+    fn default() -> Self {
+        Exact::ZERO
+    }
+}
+
+impl Add<&Exact> for &Exact {
+    type Output = Exact;
+
+    fn add(self, rhs: &Exact) -> Exact {
+        self.apply(rhs, i128::checked_add, |a, b| a + b)
+    }
+}
+
+impl Mul<&Exact> for &Exact {
+    type Output = Exact;
+
+    fn mul(self, rhs: &Exact) -> Exact {
+        self.apply(rhs, i128::checked_mul, |a, b| a * b)
+    }
+}
+
+impl Add for Exact {
+    type Output = Exact;
+
+    fn add(self, rhs: Exact) -> Exact {
+        &self + &rhs
+    }
+}
+
+impl Mul for Exact {
+    type Output = Exact;
+
+    fn mul(self, rhs: Exact) -> Exact {
+        &self * &rhs
+    }
+}
+
+impl AddAssign<&Exact> for Exact {
+    fn add_assign(&mut self, rhs: &Exact) {
+        *self = &*self + rhs;
+    }
+}
+
+impl MulAssign<&Exact> for Exact {
+    fn mul_assign(&mut self, rhs: &Exact) {
+        *self = &*self * rhs;
+    }
+}
+
+impl Sum for Exact {
+    fn sum<I: Iterator<Item = Exact>>(iter: I) -> Exact {
+        iter.fold(Exact::ZERO, |acc, value| acc + value)
+    }
+}
+
+impl Product for Exact {
+    fn product<I: Iterator<Item = Exact>>(iter: I) -> Exact {
+        iter.fold(Exact::ONE, |acc, value| acc * value)
+    }
+}
+
+impl Ord for Exact {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Exact::Small(a), Exact::Small(b)) => a.cmp(b),
+            _ => self.to_big_int().cmp(&other.to_big_int()),
+        }
+    }
+}
+
+impl PartialOrd for Exact {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<BigInt> for Exact {
+    fn from(value: BigInt) -> Self {
+        Exact::from_big_int(value)
+    }
+}
+
+macro_rules! impl_from {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Exact {
+                fn from(value: $t) -> Self {
+                    Exact::Small(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(u8, u16, u32, u64, usize, i8, i16, i32, i64, i128, isize);
+
+impl From<u128> for Exact {
+    fn from(value: u128) -> Self {
+        Exact::from_big_int(BigInt::from(value))
+    }
+}
+
+impl Display for Exact {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Exact::Small(value) => write!(f, "{}", value),
+            Exact::Big(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn promotes_on_overflow() {
+        let max = Exact::from(i128::MAX);
+        let sum = &max + &Exact::ONE;
+
+        assert!(sum.is_big());
+        assert_eq!(sum.to_string(), "170141183460469231731687303715884105728");
+        assert_eq!(sum.to_i128(), None);
+
+        let product: Exact = (0..3).map(|_| Exact::from(u64::MAX)).product();
+        assert!(product.is_big());
+        assert_eq!(
+            product.to_string(),
+            "6277101735386680762814942322444851025767571854389858533375"
+        );
+    }
+
+    #[test]
+    fn demotes_when_back_in_range() {
+        let big = Exact::from(u128::MAX);
+        assert!(big.is_big());
+
+        let small = big + Exact::from(i128::MIN) + Exact::from(i128::MIN);
+        assert_eq!(small, Exact::Small(-1));
+    }
+
+    #[test]
+    fn sum_and_ordering() {
+        let total: Exact = [1i64, 2, 3].into_iter().map(Exact::from).sum();
+        assert_eq!(total, Exact::from(6));
+        assert!(Exact::from(u128::MAX) > Exact::from(i128::MAX));
+        assert!(Exact::from(-1) < Exact::ZERO);
+    }
+}
@@ -1,3 +1,8 @@
+pub mod big_int;
+pub mod digits;
+pub mod exact;
+pub mod vector;
+
 pub type Point3i64 = (i64, i64, i64);
 
 pub fn distance_3d(p1: Point3i64, p2: Point3i64) -> i64 {
@@ -0,0 +1,148 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+/// A 2D integer vector. On grids `x` grows to the right and `y` grows downwards.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Vec2 {
+    pub const ZERO: Vec2 = Vec2::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Vec2 { x, y }
+    }
+
+    pub fn manhattan(self) -> u64 {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+
+    /// The number of king moves needed to cover the vector.
+    pub fn chebyshev(self) -> u64 {
+        self.x.unsigned_abs().max(self.y.unsigned_abs())
+    }
+
+    /// Each component reduced to -1, 0 or 1.
+    pub fn signum(self) -> Vec2 {
+        Vec2::new(self.x.signum(), self.y.signum())
+    }
+
+    /// Rotates a quarter turn clockwise, as seen on a grid with `y` pointing down.
+    pub fn rotate_right(self) -> Vec2 {
+        Vec2::new(-self.y, self.x)
+    }
+
+    /// Rotates a quarter turn counter-clockwise, as seen on a grid with `y` pointing down.
+    pub fn rotate_left(self) -> Vec2 {
+        Vec2::new(self.y, -self.x)
+    }
+}
+
+impl From<(i64, i64)> for Vec2 {
+    fn from((x, y): (i64, i64)) -> Self {
+        Vec2::new(x, y)
+    }
+}
+
+impl From<Vec2> for (i64, i64) {
+    fn from(value: Vec2) -> Self {
+        (value.x, value.y)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, rhs: Vec2) -> Vec2 {
+        Vec2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, rhs: Vec2) -> Vec2 {
+        Vec2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Mul<i64> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, rhs: i64) -> Vec2 {
+        Vec2::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, rhs: Vec2) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Vec2 {
+    fn sub_assign(&mut self, rhs: Vec2) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign<i64> for Vec2 {
+    fn mul_assign(&mut self, rhs: i64) {
+        *self = *self * rhs;
+    }
+}
+
+impl Display for Vec2 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Vec2::new(3, -4);
+        let b = Vec2::from((1, 2));
+
+        assert_eq!(a + b, Vec2::new(4, -2));
+        assert_eq!(a - b, Vec2::new(2, -6));
+        assert_eq!(a * 2, Vec2::new(6, -8));
+        assert_eq!(-a, Vec2::new(-3, 4));
+
+        let mut c = a;
+        c += b;
+        c *= 3;
+        c -= Vec2::new(12, 0);
+        assert_eq!(c, Vec2::new(0, -6));
+    }
+
+    #[test]
+    fn metrics_and_rotation() {
+        let v = Vec2::new(3, -4);
+
+        assert_eq!(v.manhattan(), 7);
+        assert_eq!(v.chebyshev(), 4);
+        assert_eq!(v.signum(), Vec2::new(1, -1));
+
+        // Up, then right, on a y-down grid
+        assert_eq!(Vec2::new(0, -1).rotate_right(), Vec2::new(1, 0));
+        assert_eq!(Vec2::new(1, 0).rotate_left(), Vec2::new(0, -1));
+        assert_eq!(v.rotate_right().rotate_left(), v);
+        assert_eq!(v.to_string(), "(3, -4)");
+    }
+}
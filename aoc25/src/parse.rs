@@ -0,0 +1,182 @@
+use crate::{
+    char_grid::{CharGrid, slice::Slice},
+    math::digits::Digits,
+};
+
+const BLANK: char = ' ';
+
+/// A group of columns cut out of a worksheet, e.g. one vertically aligned problem.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Block {
+    x: usize,
+    grid: CharGrid,
+}
+
+impl Block {
+    /// The column of the original text this block starts at.
+    pub fn x(&self) -> usize {
+        self.x
+    }
+
+    pub fn grid(&self) -> &CharGrid {
+        &self.grid
+    }
+
+    pub fn into_grid(self) -> CharGrid {
+        self.grid
+    }
+
+    /// A slice covering the whole block.
+    pub fn slice(&self) -> Slice<'_> {
+        self.grid.slice(0, 0, self.grid.width(), self.grid.height())
+    }
+}
+
+/// Which lines of a block to read numbers from, and which number comes first.
+///
+/// Only the order of the numbers changes. The digits of each number are always read left to
+/// right within a row and top to bottom within a column, most significant first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReadOrder {
+    /// One number per row, from the top row down
+    Rows,
+    /// One number per row, from the bottom row up
+    RowsReversed,
+    /// One number per column, from the leftmost column
+    Columns,
+    /// One number per column, from the rightmost column
+    ColumnsReversed,
+}
+
+/// Splits text into blocks of columns separated by columns that are blank on every line. Short
+/// lines are padded with spaces, so every block is as tall as the text.
+pub fn column_blocks(text: &str) -> Vec<Block> {
+    let lines: Vec<Vec<char>> = text.lines().map(|line| line.chars().collect()).collect();
+    let width = lines.iter().map(Vec::len).max().unwrap_or(0);
+    let is_blank = |x: usize| {
+        lines
+            .iter()
+            .all(|line| line.get(x).is_none_or(|ch| *ch == BLANK))
+    };
+
+    let mut blocks = vec![];
+    let mut x = 0;
+
+    while x < width {
+        if is_blank(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && !is_blank(x) {
+            x += 1;
+        }
+
+        let mut grid = CharGrid::with_default_char(x - start, lines.len(), BLANK);
+        for (y, line) in lines.iter().enumerate() {
+            for (dx, ch) in line.iter().skip(start).take(x - start).enumerate() {
+                grid.set(dx, y, *ch);
+            }
+        }
+
+        blocks.push(Block { x: start, grid });
+    }
+
+    blocks
+}
+
+/// Reads one number per row or column of `slice`, ignoring blanks. Rows or columns without any
+/// digits are skipped. Panics on any other char or if a number overflows `T`.
+pub fn read_numbers<T: Digits>(slice: &Slice<'_>, order: ReadOrder) -> Vec<T> {
+    let lines: Vec<Vec<char>> = match order {
+        ReadOrder::Rows | ReadOrder::RowsReversed => {
+            slice.rows_iter().map(<[char]>::to_vec).collect()
+        }
+        ReadOrder::Columns | ReadOrder::ColumnsReversed => slice.columns_iter().collect(),
+    };
+
+    let numbers = lines
+        .iter()
+        .filter(|line| line.iter().any(|ch| *ch != BLANK))
+        .map(|line| parse_number(line));
+
+    match order {
+        ReadOrder::Rows | ReadOrder::Columns => numbers.collect(),
+        ReadOrder::RowsReversed | ReadOrder::ColumnsReversed => numbers.rev().collect(),
+    }
+}
+
+fn parse_number<T: Digits>(chars: &[char]) -> T {
+    let digits = chars.iter().filter(|ch| **ch != BLANK).map(|ch| {
+        ch.to_digit(10)
+            .unwrap_or_else(|| panic!("invalid digit: {}", ch))
+    });
+
+    T::from_digits(digits).expect("number out of range")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORKSHEET: &str = "\
+123 328  51 64
+ 45 64  387 23
+  6 98  215 314
+*   +   *   +  ";
+
+    #[test]
+    fn splits_on_blank_columns() {
+        let blocks = column_blocks(WORKSHEET);
+
+        assert_eq!(blocks.len(), 4);
+        assert_eq!(
+            blocks.iter().map(Block::x).collect::<Vec<_>>(),
+            vec![0, 4, 8, 12]
+        );
+        assert_eq!(blocks[0].grid().to_raw(), "123\n 45\n  6\n*  ");
+        assert_eq!(blocks[3].grid().to_raw(), "64 \n23 \n314\n+  ");
+    }
+
+    #[test]
+    fn read_numbers_in_every_order() {
+        let blocks = column_blocks(WORKSHEET);
+        let grid = blocks[0].grid();
+        let operands = grid.slice(0, 0, grid.width(), grid.height() - 1);
+
+        assert_eq!(
+            read_numbers::<u32>(&operands, ReadOrder::Rows),
+            vec![123, 45, 6]
+        );
+        assert_eq!(
+            read_numbers::<u32>(&operands, ReadOrder::RowsReversed),
+            vec![6, 45, 123]
+        );
+        assert_eq!(
+            read_numbers::<u32>(&operands, ReadOrder::Columns),
+            vec![1, 24, 356]
+        );
+        assert_eq!(
+            read_numbers::<u32>(&operands, ReadOrder::ColumnsReversed),
+            vec![356, 24, 1]
+        );
+    }
+
+    #[test]
+    fn skips_blank_lines() {
+        let blocks = column_blocks("1  \n   \n2 3");
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(
+            read_numbers::<u8>(&blocks[0].slice(), ReadOrder::Rows),
+            vec![1, 2]
+        );
+        assert_eq!(blocks[1].x(), 2);
+    }
+
+    #[test]
+    fn empty_text() {
+        assert!(column_blocks("").is_empty());
+    }
+}
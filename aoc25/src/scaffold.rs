@@ -0,0 +1,478 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use clap::ValueEnum;
+
+const WORKSPACE_MANIFEST: &str = "Cargo.toml";
+const WORKSPACE_MARKER: &str = "[workspace]";
+const MEMBERS_START: &str = "members = [";
+const LAUNCH_CONFIG: &str = ".vscode/launch.json";
+const CONFIGURATIONS_START: &str = "\"configurations\": [";
+/// Prettier's default print width, which the launch config is formatted with
+const LAUNCH_LINE_WIDTH: usize = 80;
+const CRATE_PLACEHOLDER: &str = "{{crate}}";
+const INPUT_FILES: [&str; 2] = ["input.txt", "input_example.txt"];
+/// Build output and caches that should not be copied into a new part
+const SKIPPED_DIRS: [&str; 2] = ["target", ".cache"];
+/// Files that can refer to the crate by name, so are rewritten when copying. Everything else,
+/// input included, is copied as is
+const REWRITTEN_EXTENSIONS: [&str; 2] = ["rs", "toml"];
+
+/// The starting point for a new puzzle's `main.rs`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Template {
+    /// Loops over the input line by line
+    #[default]
+    Lines,
+    /// Loads the input into a `CharGrid`
+    Grid,
+    /// Line based, with clap arguments to override the input file
+    Args,
+}
+
+impl Template {
+    /// The source files to generate, relative to the crate directory.
+    fn files(self) -> Vec<(&'static str, &'static str)> {
+        match self {
+            Template::Lines => vec![("src/main.rs", include_str!("../templates/lines.rs.tmpl"))],
+            Template::Grid => vec![("src/main.rs", include_str!("../templates/grid.rs.tmpl"))],
+            Template::Args => vec![
+                ("src/main.rs", include_str!("../templates/args.rs.tmpl")),
+                ("src/lib.rs", include_str!("../templates/args_lib.rs.tmpl")),
+                (
+                    "src/args.rs",
+                    include_str!("../templates/args_module.rs.tmpl"),
+                ),
+            ],
+        }
+    }
+
+    fn dependencies(self) -> Vec<&'static str> {
+        let mut dependencies = vec![
+            "env_logger = \"0.11.8\"",
+            "log = \"0.4.29\"",
+            "aoc25 = { path = \"../aoc25\" }",
+        ];
+        if self == Template::Args {
+            dependencies.push("clap = { version = \"4.5.53\", features = [\"derive\"] }");
+        }
+        dependencies
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Puzzle {
+    pub day: u32,
+    pub part: u32,
+    pub name: String,
+}
+
+impl Puzzle {
+    pub fn new(day: u32, part: u32, name: &str) -> io::Result<Self> {
+        if !(1..=25).contains(&day) {
+            return Err(invalid_input(format!("invalid day: {}", day)));
+        }
+        if !(1..=2).contains(&part) {
+            return Err(invalid_input(format!("invalid part: {}", part)));
+        }
+        let is_valid_char = |ch: char| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-';
+        if name.is_empty() || !name.chars().all(is_valid_char) {
+            return Err(invalid_input(format!("invalid name: {}", name)));
+        }
+
+        Ok(Puzzle {
+            day,
+            part,
+            name: name.to_string(),
+        })
+    }
+
+    /// The package and directory name, e.g. `d4-p1-printing-department`.
+    pub fn crate_name(&self) -> String {
+        format!("d{}-p{}-{}", self.day, self.part, self.name)
+    }
+}
+
+/// Walks up from `start` to the directory whose `Cargo.toml` declares the workspace.
+pub fn find_workspace_root(start: &Path) -> io::Result<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| {
+            fs::read_to_string(dir.join(WORKSPACE_MANIFEST))
+                .is_ok_and(|manifest| manifest.contains(WORKSPACE_MARKER))
+        })
+        .map(Path::to_path_buf)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "workspace root not found"))
+}
+
+/// Finds the existing puzzle crate for a day and part, whatever its name.
+pub fn find_puzzle(root: &Path, day: u32, part: u32) -> io::Result<Option<Puzzle>> {
+    let prefix = format!("d{}-p{}-", day, part);
+
+    for entry in fs::read_dir(root)? {
+        let file_name = entry?.file_name();
+        if let Some(name) = file_name
+            .to_str()
+            .and_then(|name| name.strip_prefix(&prefix))
+        {
+            return Puzzle::new(day, part, name).map(Some);
+        }
+    }
+
+    Ok(None)
+}
+
+/// Generates a new puzzle crate from a template with empty input files, and registers it in the
+/// workspace. Returns the crate directory.
+pub fn create(root: &Path, puzzle: &Puzzle, template: Template) -> io::Result<PathBuf> {
+    let crate_name = puzzle.crate_name();
+    let dir = new_crate_dir(root, puzzle)?;
+
+    fs::create_dir_all(dir.join("src"))?;
+    fs::write(
+        dir.join(WORKSPACE_MANIFEST),
+        package_manifest(&crate_name, &template.dependencies()),
+    )?;
+    for (path, contents) in template.files() {
+        fs::write(
+            dir.join(path),
+            contents.replace(CRATE_PLACEHOLDER, &crate_ident(&crate_name)),
+        )?;
+    }
+    for input in INPUT_FILES {
+        fs::write(dir.join(input), "")?;
+    }
+
+    register(root, &crate_name)?;
+    Ok(dir)
+}
+
+/// Copies an existing puzzle crate, usually part 1, into a new one, renaming the package and any
+/// references to its library. Returns the new crate directory.
+pub fn copy(root: &Path, from: &Puzzle, to: &Puzzle) -> io::Result<PathBuf> {
+    let from_name = from.crate_name();
+    let to_name = to.crate_name();
+    let from_dir = root.join(&from_name);
+    if !from_dir.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} does not exist", from_name),
+        ));
+    }
+    let dir = new_crate_dir(root, to)?;
+
+    copy_dir(&from_dir, &dir, &|contents: String| {
+        contents
+            .replace(&format!("\"{}\"", from_name), &format!("\"{}\"", to_name))
+            .replace(&crate_ident(&from_name), &crate_ident(&to_name))
+    })?;
+
+    register(root, &to_name)?;
+    Ok(dir)
+}
+
+/// Adds a crate to the workspace members, keeping the list sorted with one member per line.
+/// Returns `None` if the manifest has no members list.
+pub fn add_workspace_member(manifest: &str, crate_name: &str) -> Option<String> {
+    let start = manifest.find(MEMBERS_START)? + MEMBERS_START.len();
+    let end = start + manifest[start..].find(']')?;
+
+    let mut members: Vec<&str> = manifest[start..end]
+        .split(',')
+        .map(|member| member.trim().trim_matches('"'))
+        .filter(|member| !member.is_empty())
+        .collect();
+    if !members.contains(&crate_name) {
+        members.push(crate_name);
+    }
+    members.sort();
+
+    let list: String = members
+        .iter()
+        .map(|member| format!("    \"{}\",\n", member))
+        .collect();
+    Some(format!(
+        "{}\n{}{}",
+        &manifest[..start],
+        list,
+        &manifest[end..]
+    ))
+}
+
+/// Prepends a debug configuration for a crate to a VS Code launch config. Returns `None` if there
+/// is no configurations list.
+pub fn add_launch_configuration(launch: &str, crate_name: &str) -> Option<String> {
+    let start = launch.find(CONFIGURATIONS_START)? + CONFIGURATIONS_START.len();
+    let rest = &launch[start..];
+
+    if rest.contains(&format!("\"--package={}\"", crate_name)) {
+        return Some(launch.to_string());
+    }
+
+    let entry = launch_configuration(crate_name);
+    let (rest, separator) = match rest.trim_start().strip_prefix(']') {
+        Some(_) => (rest.trim_start(), "\n  "),
+        None => (rest, ","),
+    };
+
+    Some(format!(
+        "{}\n{}{}{}",
+        &launch[..start],
+        entry,
+        separator,
+        rest
+    ))
+}
+
+fn launch_configuration(crate_name: &str) -> String {
+    let args = [
+        "run".to_string(),
+        format!("--bin={}", crate_name),
+        format!("--package={}", crate_name),
+    ]
+    .map(|arg| format!("\"{}\"", arg));
+
+    let inline = format!("        \"args\": [{}]", args.join(", "));
+    let args = if inline.len() <= LAUNCH_LINE_WIDTH {
+        inline
+    } else {
+        format!(
+            "        \"args\": [\n{}\n        ]",
+            args.map(|arg| format!("          {}", arg)).join(",\n")
+        )
+    };
+
+    [
+        "    {".to_string(),
+        format!("      \"name\": \"Debug executable {}\",", crate_name),
+        "      \"type\": \"lldb\",".to_string(),
+        "      \"request\": \"launch\",".to_string(),
+        "      \"env\": {".to_string(),
+        "        \"RUST_BACKTRACE\": \"full\",".to_string(),
+        "        \"RUST_LOG\": \"debug\"".to_string(),
+        "      },".to_string(),
+        "      \"cargo\": {".to_string(),
+        args,
+        "      },".to_string(),
+        "      \"args\": []".to_string(),
+        "    }".to_string(),
+    ]
+    .join("\n")
+}
+
+fn register(root: &Path, crate_name: &str) -> io::Result<()> {
+    let manifest_path = root.join(WORKSPACE_MANIFEST);
+    let manifest = fs::read_to_string(&manifest_path)?;
+    let manifest = add_workspace_member(&manifest, crate_name)
+        .ok_or_else(|| invalid_input("workspace manifest has no members list".to_string()))?;
+    fs::write(manifest_path, manifest)?;
+
+    // The launch config is optional, so only update it when present
+    let launch_path = root.join(LAUNCH_CONFIG);
+    if let Ok(launch) = fs::read_to_string(&launch_path)
+        && let Some(launch) = add_launch_configuration(&launch, crate_name)
+    {
+        fs::write(launch_path, launch)?;
+    }
+
+    Ok(())
+}
+
+/// The directory for a new puzzle, refusing if that day and part already exist under any name.
+fn new_crate_dir(root: &Path, puzzle: &Puzzle) -> io::Result<PathBuf> {
+    if let Some(existing) = find_puzzle(root, puzzle.day, puzzle.part)? {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", existing.crate_name()),
+        ));
+    }
+    Ok(root.join(puzzle.crate_name()))
+}
+
+fn package_manifest(crate_name: &str, dependencies: &[&str]) -> String {
+    format!(
+        "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2024\"\n\n[dependencies]\n{}\n",
+        crate_name,
+        dependencies.join("\n")
+    )
+}
+
+/// The name a crate is referred to by in Rust code.
+fn crate_ident(crate_name: &str) -> String {
+    crate_name.replace('-', "_")
+}
+
+/// Copies a directory recursively, passing text files through `rewrite`.
+fn copy_dir(from: &Path, to: &Path, rewrite: &dyn Fn(String) -> String) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let path = entry.path();
+        let target = to.join(entry.file_name());
+
+        if path.is_dir() {
+            if SKIPPED_DIRS
+                .iter()
+                .any(|skipped| entry.file_name() == *skipped)
+            {
+                continue;
+            }
+            copy_dir(&path, &target, rewrite)?;
+        } else if path
+            .extension()
+            .is_some_and(|extension| REWRITTEN_EXTENSIONS.iter().any(|ext| extension == *ext))
+        {
+            fs::write(target, rewrite(fs::read_to_string(&path)?))?;
+        } else {
+            fs::copy(&path, target)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn invalid_input(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = "[workspace]\nresolver = \"3\"\nmembers = [\n    \"aoc25\",\n    \"d2-p1-gift-shop\", \"d1-p1-secret-entrance\",\n]\n";
+    const LAUNCH: &str = "{\n  \"version\": \"0.2.0\",\n  \"configurations\": [\n    {\n      \"name\": \"existing\"\n    }\n  ]\n}\n";
+
+    fn temp_workspace(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("aoc25-scaffold-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join(".vscode")).unwrap();
+        fs::write(root.join(WORKSPACE_MANIFEST), MANIFEST).unwrap();
+        fs::write(root.join(LAUNCH_CONFIG), LAUNCH).unwrap();
+        root
+    }
+
+    #[test]
+    fn puzzle_validation() {
+        let puzzle = Puzzle::new(4, 1, "printing-department").unwrap();
+        assert_eq!(puzzle.crate_name(), "d4-p1-printing-department");
+
+        assert!(Puzzle::new(0, 1, "x").is_err());
+        assert!(Puzzle::new(1, 3, "x").is_err());
+        assert!(Puzzle::new(1, 1, "Gift Shop").is_err());
+        assert!(Puzzle::new(1, 1, "").is_err());
+    }
+
+    #[test]
+    fn workspace_member_sorted() {
+        let manifest = add_workspace_member(MANIFEST, "d1-p2-secret-entrance").unwrap();
+        assert_eq!(
+            manifest,
+            "[workspace]\nresolver = \"3\"\nmembers = [\n    \"aoc25\",\n    \"d1-p1-secret-entrance\",\n    \"d1-p2-secret-entrance\",\n    \"d2-p1-gift-shop\",\n]\n"
+        );
+        assert_eq!(
+            add_workspace_member(&manifest, "d1-p2-secret-entrance").unwrap(),
+            manifest
+        );
+        assert_eq!(add_workspace_member("[package]", "x"), None);
+    }
+
+    #[test]
+    fn launch_configuration_prepended() {
+        let launch = add_launch_configuration(LAUNCH, "d1-p1-a").unwrap();
+        assert!(launch.starts_with(
+            "{\n  \"version\": \"0.2.0\",\n  \"configurations\": [\n    {\n      \"name\": \"Debug executable d1-p1-a\","
+        ));
+        assert!(launch.contains("\"args\": [\"run\", \"--bin=d1-p1-a\", \"--package=d1-p1-a\"]"));
+        assert!(launch.contains("    },\n    {\n      \"name\": \"existing\""));
+        assert_eq!(
+            add_launch_configuration(&launch, "d1-p1-a").unwrap(),
+            launch
+        );
+
+        // Long argument lists wrap like prettier does
+        let launch = add_launch_configuration(LAUNCH, "d9-p2-movie-theater").unwrap();
+        assert!(launch.contains("\"args\": [\n          \"run\",\n"));
+
+        let empty = "{\n  \"configurations\": []\n}\n";
+        let launch = add_launch_configuration(empty, "d1-p1-a").unwrap();
+        assert!(launch.ends_with("    }\n  ]\n}\n"));
+    }
+
+    #[test]
+    fn create_from_template() {
+        let root = temp_workspace("create");
+        let puzzle = Puzzle::new(11, 1, "reactor").unwrap();
+
+        let dir = create(&root, &puzzle, Template::Args).unwrap();
+
+        let main = fs::read_to_string(dir.join("src/main.rs")).unwrap();
+        assert!(main.contains("use d11_p1_reactor::args::Args;"));
+        assert!(dir.join("src/args.rs").exists());
+        assert_eq!(fs::read_to_string(dir.join("input.txt")).unwrap(), "");
+        assert!(
+            fs::read_to_string(dir.join("Cargo.toml"))
+                .unwrap()
+                .contains("name = \"d11-p1-reactor\"\n")
+        );
+        assert!(
+            fs::read_to_string(root.join(WORKSPACE_MANIFEST))
+                .unwrap()
+                .contains("\"d11-p1-reactor\",")
+        );
+        assert!(
+            fs::read_to_string(root.join(LAUNCH_CONFIG))
+                .unwrap()
+                .contains("--package=d11-p1-reactor")
+        );
+
+        let error = create(&root, &puzzle, Template::Lines).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::AlreadyExists);
+        let renamed = Puzzle::new(11, 1, "other").unwrap();
+        let error = create(&root, &renamed, Template::Lines).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::AlreadyExists);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn copy_part_one() {
+        let root = temp_workspace("copy");
+        let part_one = Puzzle::new(11, 1, "reactor").unwrap();
+        create(&root, &part_one, Template::Args).unwrap();
+        fs::write(root.join("d11-p1-reactor/input.txt"), "data d11_p1_reactor").unwrap();
+        fs::create_dir_all(root.join("d11-p1-reactor/.cache")).unwrap();
+
+        assert_eq!(find_puzzle(&root, 11, 1).unwrap(), Some(part_one.clone()));
+        assert_eq!(find_puzzle(&root, 11, 2).unwrap(), None);
+
+        let part_two = Puzzle::new(11, 2, "reactor").unwrap();
+        let dir = copy(&root, &part_one, &part_two).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.join("input.txt")).unwrap(),
+            "data d11_p1_reactor"
+        );
+        assert!(!dir.join(".cache").exists());
+        assert!(
+            fs::read_to_string(dir.join("src/main.rs"))
+                .unwrap()
+                .contains("use d11_p2_reactor::args::Args;")
+        );
+        assert!(
+            fs::read_to_string(dir.join("Cargo.toml"))
+                .unwrap()
+                .contains("name = \"d11-p2-reactor\"\n")
+        );
+        assert!(
+            fs::read_to_string(root.join(WORKSPACE_MANIFEST))
+                .unwrap()
+                .contains("    \"d11-p1-reactor\",\n    \"d11-p2-reactor\",\n")
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
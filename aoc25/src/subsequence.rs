@@ -0,0 +1,129 @@
+use std::cmp::Ordering;
+
+/// Picks `k` items, keeping their order, so the result is the lexicographically greatest
+/// possible. For digits this is the largest number that can be formed.
+///
+/// Runs in O(n) with a monotonic stack. Panics if `k` exceeds the number of items.
+pub fn max_subsequence<T: Ord + Clone>(items: &[T], k: usize) -> Vec<T> {
+    select(items, k, Ordering::Less)
+        .into_iter()
+        .map(|i| items[i].clone())
+        .collect()
+}
+
+/// Picks `k` items, keeping their order, so the result is the lexicographically smallest
+/// possible.
+pub fn min_subsequence<T: Ord + Clone>(items: &[T], k: usize) -> Vec<T> {
+    select(items, k, Ordering::Greater)
+        .into_iter()
+        .map(|i| items[i].clone())
+        .collect()
+}
+
+/// The indices chosen by [`max_subsequence`], in ascending order.
+pub fn max_subsequence_indices<T: Ord>(items: &[T], k: usize) -> Vec<usize> {
+    select(items, k, Ordering::Less)
+}
+
+/// The indices chosen by [`min_subsequence`], in ascending order.
+pub fn min_subsequence_indices<T: Ord>(items: &[T], k: usize) -> Vec<usize> {
+    select(items, k, Ordering::Greater)
+}
+
+/// Keeps a stack of chosen indices, discarding the top whenever the next item compares better
+/// and there are still items to spare. `worse` is how a discarded item compares to its
+/// replacement.
+fn select<T: Ord>(items: &[T], k: usize, worse: Ordering) -> Vec<usize> {
+    assert!(
+        k <= items.len(),
+        "cannot pick {} items from {}",
+        k,
+        items.len()
+    );
+
+    let mut to_drop = items.len() - k;
+    let mut stack: Vec<usize> = Vec::with_capacity(items.len());
+
+    for (i, item) in items.iter().enumerate() {
+        while to_drop > 0
+            && let Some(top) = stack.last()
+            && items[*top].cmp(item) == worse
+        {
+            stack.pop();
+            to_drop -= 1;
+        }
+        stack.push(i);
+    }
+
+    stack.truncate(k);
+    stack
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(s: &str) -> Vec<u32> {
+        s.chars().map(|c| c.to_digit(10).unwrap()).collect()
+    }
+
+    /// Tries every way of picking k items.
+    fn brute_force(items: &[u32], k: usize, max: bool) -> Vec<u32> {
+        let n = items.len();
+        (0u32..1 << n)
+            .filter(|mask| mask.count_ones() as usize == k)
+            .map(|mask| {
+                (0..n)
+                    .filter(|i| mask >> i & 1 == 1)
+                    .map(|i| items[i])
+                    .collect::<Vec<_>>()
+            })
+            .reduce(|a, b| if (b > a) == max { b } else { a })
+            .unwrap()
+    }
+
+    #[test]
+    fn max_subsequence_digits() {
+        assert_eq!(max_subsequence(&digits("987654321111111"), 2), digits("98"));
+        assert_eq!(max_subsequence(&digits("811111111111119"), 2), digits("89"));
+        assert_eq!(
+            max_subsequence(&digits("234234234234278"), 12),
+            digits("434234234278")
+        );
+        assert_eq!(
+            max_subsequence(&digits("818181911112111"), 12),
+            digits("888911112111")
+        );
+    }
+
+    #[test]
+    fn min_subsequence_digits() {
+        assert_eq!(min_subsequence(&digits("1432219"), 4), digits("1219"));
+        assert_eq!(min_subsequence_indices(&digits("321"), 1), vec![2]);
+    }
+
+    #[test]
+    fn edge_cases() {
+        assert!(max_subsequence(&digits("123"), 0).is_empty());
+        assert_eq!(max_subsequence(&digits("123"), 3), digits("123"));
+        assert_eq!(max_subsequence(&["b", "a", "c"], 2), vec!["b", "c"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn k_too_large() {
+        max_subsequence(&[1, 2], 3);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let input = digits("3141592653589793");
+        for n in 1..=input.len().min(12) {
+            for k in 0..=n {
+                let items = &input[..n];
+                assert_eq!(max_subsequence(items, k), brute_force(items, k, true));
+                assert_eq!(min_subsequence(items, k), brute_force(items, k, false));
+            }
+        }
+    }
+}
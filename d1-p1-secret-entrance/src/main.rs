@@ -1,17 +1,30 @@
+use aoc25::dial::Dial;
+
 const INPUT: &str = include_str!("../input_example.txt");
-const MAX_POSITION: i32 = 99;
-const INITIAL_POSITION: i32 = 50;
+const DIAL_SIZE: i64 = 100;
+const INITIAL_POSITION: i64 = 50;
+const ZERO: i64 = 0;
 
 #[derive(Debug)]
 struct Rotation {
     right: bool,
-    distance: i32,
+    distance: i64,
+}
+
+impl Rotation {
+    fn delta(&self) -> i64 {
+        if self.right {
+            self.distance
+        } else {
+            -self.distance
+        }
+    }
 }
 
 impl From<&str> for Rotation {
     fn from(line: &str) -> Self {
         let direction = &line[0..1];
-        let distance = line[1..].parse::<i32>().unwrap();
+        let distance = line[1..].parse::<i64>().unwrap();
         Rotation {
             right: direction == "R",
             distance,
@@ -21,23 +34,16 @@ impl From<&str> for Rotation {
 
 fn main() {
     let rotations = INPUT.lines().map(Rotation::from).collect::<Vec<Rotation>>();
-    let mut position: i32 = INITIAL_POSITION;
+    let mut dial = Dial::new(DIAL_SIZE, INITIAL_POSITION);
     let mut password = 0;
 
     for rotation in &rotations {
-        let delta = if rotation.right {
-            rotation.distance
-        } else {
-            -rotation.distance
-        };
-
-        let new_position =
-            ((position + delta) % (MAX_POSITION + 1) + (MAX_POSITION + 1)) % (MAX_POSITION + 1);
+        let position = dial.position();
+        let new_position = dial.rotate(rotation.delta());
 
         println!("Moving from {} to {}", position, new_position);
-        position = new_position;
 
-        if position == 0 {
+        if new_position == ZERO {
             password += 1;
         }
     }
@@ -1,19 +1,32 @@
 use std::fmt::{self, Display, Formatter};
 
+use aoc25::dial::Dial;
+
 const INPUT: &str = include_str!("../input.txt");
-const MAX_POSITION: i32 = 99;
-const INITIAL_POSITION: i32 = 50;
+const DIAL_SIZE: i64 = 100;
+const INITIAL_POSITION: i64 = 50;
+const ZERO: i64 = 0;
 
 #[derive(Debug)]
 struct Rotation {
     right: bool,
-    distance: i32,
+    distance: i64,
+}
+
+impl Rotation {
+    fn delta(&self) -> i64 {
+        if self.right {
+            self.distance
+        } else {
+            -self.distance
+        }
+    }
 }
 
 impl From<&str> for Rotation {
     fn from(line: &str) -> Self {
         let direction = &line[0..1];
-        let distance = line[1..].parse::<i32>().unwrap();
+        let distance = line[1..].parse::<i64>().unwrap();
         Rotation {
             right: direction == "R",
             distance,
@@ -29,43 +42,22 @@ impl Display for Rotation {
 
 fn main() {
     let rotations = INPUT.lines().map(Rotation::from).collect::<Vec<Rotation>>();
-    let mut position: i32 = INITIAL_POSITION;
+    let mut dial = Dial::new(DIAL_SIZE, INITIAL_POSITION);
     let mut password = 0;
 
     for rotation in &rotations {
-        let distance = rotation.distance;
-        let delta = if rotation.right { distance } else { -distance };
-
-        let new_position =
-            ((position + delta) % (MAX_POSITION + 1) + (MAX_POSITION + 1)) % (MAX_POSITION + 1);
-
-        let zero_passes = if rotation.right {
-            let zero_distance = (MAX_POSITION + 1) - position;
-
-            if distance >= zero_distance {
-                1 + (distance - zero_distance) / (MAX_POSITION + 1)
-            } else {
-                0
-            }
-        } else {
-            let zero_distance = position;
-
-            if distance >= zero_distance && zero_distance > 0 {
-                1 + (distance - zero_distance) / (MAX_POSITION + 1)
-            } else if distance >= zero_distance && zero_distance == 0 {
-                distance / (MAX_POSITION + 1)
-            } else {
-                0
-            }
-        };
+        let position = dial.position();
+        let zero_passes = dial.rotate_counting(rotation.delta(), ZERO);
 
         println!(
             "[{}] Moving from {} to {} (crossed zero {} times)",
-            rotation, position, new_position, zero_passes
+            rotation,
+            position,
+            dial.position(),
+            zero_passes
         );
 
         password += zero_passes;
-        position = new_position;
     }
 
     println!("Password: {}", password);
@@ -1,13 +1,13 @@
 use std::fmt::{self, Display, Formatter};
 
+use aoc25::{bitset::BitSet, gf2::Matrix};
 use itertools::Itertools;
+use log::debug;
 
 #[cfg(debug_assertions)]
 const INPUT: &str = include_str!("../input_example.txt");
 #[cfg(not(debug_assertions))]
 const INPUT: &str = include_str!("../input.txt");
-const ON: char = '#';
-const OFF: char = '.';
 const DIAGRAM_START: char = '[';
 const DIAGRAM_END: char = ']';
 const BUTTON_START: char = '(';
@@ -24,53 +24,33 @@ struct Machine {
 
 impl Machine {
     pub fn find_min_button_sequence_len(&self) -> usize {
-        let mut n = 1;
-
-        loop {
-            let sequences = (0..n).map(|_| &self.buttons).multi_cartesian_product();
-
-            for (sequence_num, sequence) in sequences.enumerate() {
-                let mut state = IndicatorLights(vec![false; self.desired_state.0.len()]);
-                let mut pressed: Vec<&Button> = vec![];
-
-                for button in sequence {
-                    debug_sequence(sequence_num, &state, &pressed);
-
-                    // Press button
-                    for wire in &button.0 {
-                        state.0[*wire as usize] = !state.0[*wire as usize];
-                    }
-
-                    pressed.push(button);
-                }
-
-                debug_sequence(sequence_num, &state, &pressed);
-
-                if cfg!(debug_assertions) {
-                    println!();
-                }
-
-                if state.0 == self.desired_state.0 {
-                    return pressed.len();
-                }
-            }
-
-            n += 1;
-        }
-    }
-}
-
-#[cfg(debug_assertions)]
-fn debug_sequence(sequence_num: usize, state: &IndicatorLights, pressed: &Vec<&Button>) {
-    print!("\r[{}]: {}", sequence_num, state);
-    for b in pressed {
-        print!(" {}", b);
+        let num_lights = self.desired_state.0.len();
+        let columns: Vec<BitSet> = self
+            .buttons
+            .iter()
+            .map(|button| button.to_bit_set(num_lights))
+            .collect();
+
+        let solution = Matrix::from_columns(num_lights, &columns)
+            .solve(&self.desired_state.0)
+            .expect("lights cannot reach the desired state");
+
+        // Pressing a button twice cancels out, so each button is pressed at most once
+        let presses = solution.min_weight();
+
+        debug!(
+            "{}: {}",
+            self.desired_state,
+            presses
+                .ones_iter()
+                .map(|i| self.buttons[i].to_string())
+                .join(" ")
+        );
+
+        presses.count_ones()
     }
 }
 
-#[cfg(not(debug_assertions))]
-fn debug_sequence(_sequence_num: usize, _state: &IndicatorLights, _pressed: &Vec<&Button>) {}
-
 impl From<&str> for Machine {
     fn from(value: &str) -> Self {
         let parts = value.split_whitespace();
@@ -113,29 +93,33 @@ fn parse_input_part<'a>(part: &'a str, end: &char, kind: &str) -> &'a str {
 }
 
 #[derive(Debug, Default, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
-struct IndicatorLights(Vec<bool>);
+struct IndicatorLights(BitSet);
 
 impl From<&str> for IndicatorLights {
     fn from(value: &str) -> Self {
-        Self(value.chars().map(|ch| ch == ON).collect())
+        Self(value.into())
     }
 }
 
 impl Display for IndicatorLights {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "[")?;
-
-        for light in &self.0 {
-            write!(f, "{}", if *light { ON } else { OFF })?;
-        }
-
-        write!(f, "]")
+        write!(f, "{}", self.0)
     }
 }
 
 #[derive(Debug, Default, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 struct Button(Vec<u32>);
 
+impl Button {
+    fn to_bit_set(&self, num_lights: usize) -> BitSet {
+        let mut result = BitSet::new(num_lights);
+        for wire in &self.0 {
+            result.flip(*wire as usize);
+        }
+        result
+    }
+}
+
 impl From<&str> for Button {
     fn from(value: &str) -> Self {
         Self(parse_u32_list(value))
@@ -0,0 +1,161 @@
+use std::fmt::{self, Display, Formatter};
+
+use aoc25::{bitset::BitSet, ilp::System};
+use itertools::Itertools;
+use log::debug;
+
+#[cfg(debug_assertions)]
+const INPUT: &str = include_str!("../input_example.txt");
+#[cfg(not(debug_assertions))]
+const INPUT: &str = include_str!("../input.txt");
+const DIAGRAM_START: char = '[';
+const DIAGRAM_END: char = ']';
+const BUTTON_START: char = '(';
+const BUTTON_END: char = ')';
+const JOLTAGES_START: char = '{';
+const JOLTAGES_END: char = '}';
+
+#[derive(Debug, Default, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+struct Machine {
+    desired_state: IndicatorLights,
+    buttons: Vec<Button>,
+    joltages: Joltages,
+}
+
+impl Machine {
+    pub fn find_min_button_presses(&self) -> i64 {
+        let targets: Vec<i64> = self.joltages.0.iter().map(|j| *j as i64).collect();
+        let wires = self
+            .buttons
+            .iter()
+            .map(|button| button.0.iter().map(|wire| *wire as usize));
+
+        let presses = System::from_increments(wires, targets)
+            .minimize_sum()
+            .expect("joltages cannot be reached");
+
+        debug!(
+            "{} {}: {}",
+            self.desired_state,
+            self.joltages,
+            self.buttons
+                .iter()
+                .zip(&presses)
+                .filter(|(_, n)| **n > 0)
+                .map(|(button, n)| format!("{}x{}", button, n))
+                .join(" ")
+        );
+
+        presses.iter().sum()
+    }
+}
+
+impl From<&str> for Machine {
+    fn from(value: &str) -> Self {
+        let parts = value.split_whitespace();
+        let mut desired_state: Option<IndicatorLights> = None;
+        let mut buttons: Vec<Button> = vec![];
+        let mut joltages: Option<Joltages> = None;
+
+        for part in parts {
+            if part.starts_with(DIAGRAM_START) {
+                desired_state = Some(parse_input_part(part, &DIAGRAM_END, "diagram").into());
+                continue;
+            }
+
+            if part.starts_with(BUTTON_START) {
+                buttons.push(parse_input_part(part, &BUTTON_END, "button").into());
+                continue;
+            }
+
+            if part.starts_with(JOLTAGES_START) {
+                joltages = Some(parse_input_part(part, &JOLTAGES_END, "joltages").into());
+            }
+        }
+
+        let desired_state = desired_state.expect("diagram not found");
+        let joltages = joltages.expect("joltages not found");
+
+        Self {
+            desired_state,
+            buttons,
+            joltages,
+        }
+    }
+}
+
+fn parse_input_part<'a>(part: &'a str, end: &char, kind: &str) -> &'a str {
+    let end_pos = part
+        .find(*end)
+        .unwrap_or_else(|| panic!("missing closing bracket for {}", kind));
+    &part[1..end_pos]
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+struct IndicatorLights(BitSet);
+
+impl From<&str> for IndicatorLights {
+    fn from(value: &str) -> Self {
+        Self(value.into())
+    }
+}
+
+impl Display for IndicatorLights {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+struct Button(Vec<u32>);
+
+impl From<&str> for Button {
+    fn from(value: &str) -> Self {
+        Self(parse_u32_list(value))
+    }
+}
+
+impl Display for Button {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "({})", self.0.iter().join(", "))
+    }
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+struct Joltages(Vec<u32>);
+
+impl From<&str> for Joltages {
+    fn from(value: &str) -> Self {
+        Self(parse_u32_list(value))
+    }
+}
+
+impl Display for Joltages {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{{{}}}", self.0.iter().join(","))
+    }
+}
+
+fn parse_u32_list(value: &str) -> Vec<u32> {
+    value
+        .split(",")
+        .map(|value| value.parse().expect("invalid button digit"))
+        .collect()
+}
+
+fn main() {
+    env_logger::builder().format_timestamp(None).init();
+
+    let machines: Vec<_> = INPUT.lines().map(Machine::from).collect();
+    let num_machines = machines.len();
+    let mut answer = 0;
+
+    for (i, machine) in machines.iter().enumerate() {
+        let progress = i as f64 / num_machines as f64 * 100.0;
+        print!("\rProgress: {:.2}% ({}/{})", progress, i, num_machines);
+        answer += machine.find_min_button_presses();
+    }
+
+    println!();
+    println!("Answer: {}", answer);
+}
@@ -1,19 +1,19 @@
 use std::ops::RangeInclusive;
 
-use aoc25::range::Range;
+use aoc25::{
+    digit_dp::{self, Repeats},
+    range::Range,
+};
 
 const INPUT: &str = include_str!("../input.txt");
+const REPEATS: Repeats = Repeats::Exactly(2);
 
 fn main() {
     let ranges = parse_ranges();
-    let mut answer: u64 = 0;
+    let mut answer: u128 = 0;
 
     for range in ranges {
-        for id in range {
-            if !is_valid_id(id) {
-                answer += id;
-            }
-        }
+        answer += digit_dp::sum_periodic(range, REPEATS);
     }
 
     println!("Answer: {}", answer);
@@ -27,34 +27,14 @@ fn parse_ranges() -> Vec<RangeInclusive<u64>> {
         .collect()
 }
 
-fn is_valid_id(id: u64) -> bool {
-    let id_str = id.to_string();
-    let num_digits = id_str.len();
-    let digits = id_str.chars();
-    let mut sequence = String::new();
-
-    for (i, digit) in digits.enumerate() {
-        if !sequence.is_empty() {
-            // Check current sequence
-            let remaining_digits = num_digits - i;
-            if remaining_digits == sequence.len() {
-                // We can fit a second repeat of the sequence in the remaining digits
-                if id_str[i..] == sequence {
-                    // The sequence repeats a second time
-                    return false;
-                }
-            }
-        }
-        sequence.push(digit);
-    }
-
-    true
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn is_valid_id(id: u64) -> bool {
+        digit_dp::count_periodic(id..=id, REPEATS) == 0
+    }
+
     #[test]
     fn is_valid_id_repeated_sequence() {
         assert!(!is_valid_id(11));
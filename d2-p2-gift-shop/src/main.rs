@@ -1,23 +1,25 @@
 use std::ops::RangeInclusive;
 
-use aoc25::range::Range;
+use aoc25::{
+    digit_dp::{self, Repeats},
+    range::Range,
+};
 use log::debug;
 
 const INPUT: &str = include_str!("../input.txt");
+const REPEATS: Repeats = Repeats::AtLeast(2);
 
 fn main() {
     env_logger::builder().format_timestamp(None).init();
 
     let ranges = parse_ranges();
-    let mut answer: u64 = 0;
+    let mut answer: u128 = 0;
 
     for range in ranges {
         debug!("{:?}", range);
-        for id in range {
-            if !is_valid_id(id) {
-                debug!(" Invalid: {}", id);
-                answer += id;
-            }
+        for id in digit_dp::periodic_iter(range, REPEATS) {
+            debug!(" Invalid: {}", id);
+            answer += id as u128;
         }
     }
 
@@ -32,35 +34,14 @@ fn parse_ranges() -> Vec<RangeInclusive<u64>> {
         .collect()
 }
 
-fn is_valid_id(id: u64) -> bool {
-    let id_str = id.to_string();
-    let num_digits = id_str.len();
-    let digits = id_str.chars();
-    let mut sequence = String::new();
-
-    for (i, digit) in digits.enumerate() {
-        if !sequence.is_empty() {
-            // Check current sequence
-            let remaining_digits = num_digits - i;
-            if remaining_digits.is_multiple_of(sequence.len()) {
-                // We can fit one or more of the sequence in the remaining digits
-                let repeated_sequence = sequence.repeat(remaining_digits / sequence.len());
-                if id_str[i..] == repeated_sequence {
-                    // The rest of the string repeats the current sequence
-                    return false;
-                }
-            }
-        }
-        sequence.push(digit);
-    }
-
-    true
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn is_valid_id(id: u64) -> bool {
+        digit_dp::count_periodic(id..=id, REPEATS) == 0
+    }
+
     #[test]
     fn is_valid_id_repeated_sequence() {
         assert!(!is_valid_id(11));
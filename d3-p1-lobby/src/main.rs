@@ -1,44 +1,17 @@
 use std::fmt::{self, Display, Formatter};
 
+use aoc25::{math::digits::Digits, subsequence};
 use log::debug;
 
 const INPUT: &str = include_str!("../input.txt");
+const POWERED_BATTERIES: usize = 2;
 
 struct Bank(Vec<u32>);
 
 impl Bank {
-    fn find_max_joltage(&self) -> u32 {
-        let data = &self.0;
-
-        // Find the max digit excluding the final index
-        let mut first_digit: Option<u32> = None;
-        let mut first_digit_index: Option<usize> = None;
-
-        for (i, digit) in data[..data.len() - 1].iter().enumerate() {
-            match first_digit {
-                None => {
-                    first_digit = Some(*digit);
-                    first_digit_index = Some(i);
-                }
-                Some(current) => {
-                    if *digit > current {
-                        first_digit = Some(*digit);
-                        first_digit_index = Some(i);
-                    }
-                }
-            }
-        }
-
-        let first_digit = first_digit.expect("could not find first digit");
-        let first_digit_index = first_digit_index.expect("could not find first digit index");
-
-        // Find the max digit to the right of the first digit
-        let second_digit = data[first_digit_index + 1..]
-            .iter()
-            .max()
-            .expect("could not find second digit");
-
-        first_digit * 10 + second_digit
+    fn find_max_joltage(&self, num_batteries: usize) -> u32 {
+        let digits = subsequence::max_subsequence(&self.0, num_batteries);
+        u32::from_digits(digits).expect("joltage overflow")
     }
 }
 
@@ -60,7 +33,7 @@ fn main() {
     let mut joltage = 0;
 
     for bank in banks {
-        let bank_joltage = bank.find_max_joltage();
+        let bank_joltage = bank.find_max_joltage(POWERED_BATTERIES);
         debug!("{} -> {}", bank, bank_joltage);
         joltage += bank_joltage;
     }
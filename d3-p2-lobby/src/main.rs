@@ -1,5 +1,6 @@
 use std::fmt::{self, Display, Formatter};
 
+use aoc25::{math::digits::Digits, subsequence};
 use log::debug;
 
 const INPUT: &str = include_str!("../input.txt");
@@ -8,47 +9,9 @@ const POWERED_BATTERIES: usize = 12;
 struct Bank(Vec<u32>);
 
 impl Bank {
-    fn find_max_joltage(&self) -> u64 {
-        let data = &self.0;
-        let mut digits = [0; POWERED_BATTERIES];
-        let mut cursor: usize = 0;
-
-        // For each digit slot
-        for (i, slot) in digits.iter_mut().enumerate() {
-            // Find the max of the eligible digits
-            let num_remaining_digits = POWERED_BATTERIES - i;
-            let max_window_size = data.len() - cursor;
-            let window_size = max_window_size - num_remaining_digits + 1;
-
-            let eligible_digits = &data[cursor..cursor + window_size];
-            let mut candidate: Option<u32> = None;
-            let mut candidate_index: Option<usize> = None;
-
-            for (j, digit) in eligible_digits.iter().enumerate() {
-                match candidate {
-                    None => {
-                        // Initialize candidate digit
-                        candidate = Some(*digit);
-                        candidate_index = Some(j + cursor);
-                    }
-                    Some(current) => {
-                        if *digit > current {
-                            // Found a higher eligible digit
-                            candidate = Some(*digit);
-                            candidate_index = Some(j + cursor);
-                        }
-                    }
-                }
-            }
-
-            // Assign the candidate to the current digit slot
-            *slot = candidate.expect("could not find digit");
-
-            // Move the cursor past the new digit
-            cursor = candidate_index.expect("could not find digit index") + 1;
-        }
-
-        digits.iter().fold(0u64, |acc, &d| acc * 10 + d as u64)
+    fn find_max_joltage(&self, num_batteries: usize) -> u64 {
+        let digits = subsequence::max_subsequence(&self.0, num_batteries);
+        u64::from_digits(digits).expect("joltage overflow")
     }
 }
 
@@ -70,7 +33,7 @@ fn main() {
     let mut joltage = 0;
 
     for bank in banks {
-        let bank_joltage = bank.find_max_joltage();
+        let bank_joltage = bank.find_max_joltage(POWERED_BATTERIES);
         debug!("{} -> {}", bank, bank_joltage);
         joltage += bank_joltage;
     }
@@ -1,4 +1,5 @@
 use aoc25::char_grid::CharGrid;
+use log::debug;
 
 const INPUT: &str = include_str!("../input.txt");
 const MAX_ADJACENT_ROLLS: u32 = 3;
@@ -13,6 +14,11 @@ fn main() {
 
     loop {
         let num_rolls = calc_num_accessible_rolls(&input, &mut output);
+        debug!(
+            "Removed {} rolls:\n{}",
+            num_rolls,
+            input.diff(&output).render_overlay()
+        );
         input = output;
         output = input.clone();
         answer += num_rolls;
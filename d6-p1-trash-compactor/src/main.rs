@@ -1,4 +1,10 @@
+use aoc25::{
+    math::exact::Exact,
+    parse::{self, Block, ReadOrder},
+};
+
 const INPUT: &str = include_str!("../input.txt");
+const OPERAND_ORDER: ReadOrder = ReadOrder::Rows;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 enum Operator {
@@ -13,62 +19,48 @@ struct Problem {
 }
 
 impl Problem {
-    pub fn new(operator: Operator) -> Self {
+    /// Reads a problem from its block: the operands above and the operator in the bottom row.
+    pub fn from_block(block: &Block) -> Self {
+        let grid = block.grid();
+        let operands = grid.slice(0, 0, grid.width(), grid.height() - 1);
+        let operator = grid
+            .get_row(grid.height() - 1)
+            .iter()
+            .find(|ch| **ch != ' ')
+            .expect("missing operator");
+
         Problem {
-            operator,
-            operands: vec![],
+            operands: parse::read_numbers(&operands, OPERAND_ORDER),
+            operator: match operator {
+                '*' => Operator::Multiply,
+                '+' => Operator::Add,
+                _ => panic!("unknown operator: {}", operator),
+            },
         }
     }
 
-    pub fn eval(&self) -> i64 {
-        let operands = &self.operands;
-
-        if operands.is_empty() {
-            return 0;
+    pub fn eval(&self) -> Exact {
+        if self.operands.is_empty() {
+            return Exact::ZERO;
         }
 
-        operands[1..]
-            .iter()
-            .fold(operands[0], |acc, operand| match self.operator {
-                Operator::Multiply => acc * operand,
-                Operator::Add => acc + operand,
-            })
+        let operands = self.operands.iter().copied().map(Exact::from);
+        match self.operator {
+            Operator::Multiply => operands.product(),
+            Operator::Add => operands.sum(),
+        }
     }
 }
 
 fn main() {
     env_logger::builder().format_timestamp(None).init();
 
-    let lines: Vec<&str> = INPUT.trim().lines().collect();
-    let mut problems: Vec<Problem> = init_problems(&lines);
-
-    load_operands(&lines, &mut problems);
+    let problems: Vec<Problem> = parse::column_blocks(INPUT.trim_end())
+        .iter()
+        .map(Problem::from_block)
+        .collect();
 
-    let answer: i64 = problems.iter().map(Problem::eval).sum();
+    let answer: Exact = problems.iter().map(Problem::eval).sum();
 
     println!("Answer: {}", answer);
 }
-
-fn load_operands(lines: &[&str], problems: &mut [Problem]) {
-    for line in &lines[..lines.len() - 1] {
-        for (problem_index, operand_str) in line.split_whitespace().enumerate() {
-            let problem = &mut problems[problem_index];
-            let operand = operand_str.parse::<i64>().expect("invalid operand");
-            problem.operands.push(operand);
-        }
-    }
-}
-
-fn init_problems(lines: &[&str]) -> Vec<Problem> {
-    lines
-        .last()
-        .expect("invalid input")
-        .split_whitespace()
-        .map(|operator| match operator {
-            "*" => Operator::Multiply,
-            "+" => Operator::Add,
-            _ => panic!("unknown operator: {}", operator),
-        })
-        .map(Problem::new)
-        .collect()
-}
@@ -1,19 +1,25 @@
-use std::{thread, time::Duration};
+use std::time::Duration;
 
-use aoc25::{char_grid::CharGrid, io};
+use aoc25::{
+    char_grid::{
+        CharGrid,
+        beam::{BeamEngine, BeamRules, Behavior},
+    },
+    debugger::{Debugger, Vars},
+    direction::Direction,
+};
 
 const INPUT: &str = include_str!("../input.txt");
 const START: char = 'S';
 const BEAM: char = '|';
 const SPLITTER: char = '^';
+const DELAY: Duration = Duration::from_millis(50);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 struct TachyonManifold {
     grid: CharGrid,
     start_x: usize,
     start_y: usize,
-    current_y: usize,
-    num_splits: usize,
 }
 
 impl TachyonManifold {
@@ -23,79 +29,33 @@ impl TachyonManifold {
             grid,
             start_x,
             start_y,
-            current_y: start_y,
-            num_splits: 0,
         }
     }
 
-    pub fn next(&mut self) -> bool {
-        let start_y = self.start_y;
-        let start_x = self.start_x;
-        let y = self.current_y;
-        let grid = &mut self.grid;
-
-        if y > grid.height() - 1 {
-            return false;
-        }
-
-        if y == start_y {
-            grid.set(start_x, start_y + 1, BEAM);
-            self.current_y += 1;
-            return true;
-        }
-
-        let row = grid.get_row(y);
-        let mut new_beams: Vec<(usize, usize)> = vec![];
-
-        for (x, ch) in row.iter().enumerate() {
-            if *ch != SPLITTER {
-                continue;
-            }
-
-            let above = grid.get(x, y - 1);
-
-            if above != BEAM {
-                continue;
-            }
-
-            // Beam is hitting splitter
-            let mut did_split = false;
-            new_beams.append(&mut Self::cast_beam(grid, x - 1, y, &mut did_split));
-            new_beams.append(&mut Self::cast_beam(grid, x + 1, y, &mut did_split));
-
-            if did_split {
-                self.num_splits += 1;
-            }
-        }
-
-        for (x, y) in new_beams {
-            grid.set(x, y, BEAM);
-        }
-
-        self.current_y += 1;
-        true
-    }
-
-    fn cast_beam(
-        grid: &CharGrid,
-        x: usize,
-        mut y: usize,
-        did_split: &mut bool,
-    ) -> Vec<(usize, usize)> {
-        let mut positions: Vec<(usize, usize)> = vec![];
-
-        while grid.contains(x as i64, y as i64) && grid.get(x, y) != SPLITTER {
-            *did_split = true;
-            positions.push((x, y));
-            y += 1;
-        }
-
-        positions
+    /// Beams fall straight down, and a splitter sends them out to either side to fall from there.
+    /// A beam sent sideways into another splitter stops. Beams landing on the same cell merge.
+    pub fn beams(&self) -> BeamEngine<'_> {
+        let rules = BeamRules::new()
+            .with_default(Behavior::Redirect(Direction::Down))
+            .with(START, Behavior::Redirect(Direction::Down))
+            .with(
+                SPLITTER,
+                Behavior::SplitOrAbsorb(&[Direction::Left, Direction::Right]),
+            );
+
+        let mut engine = BeamEngine::new(&self.grid, rules);
+        engine.emit((self.start_x, self.start_y), Direction::Down);
+        engine
     }
 
-    pub fn print(&self) {
-        io::clear_screen();
-        println!("{}", self.grid);
+    pub fn vars(engine: &BeamEngine) -> Vars {
+        let stats = engine.stats();
+        Vars::new()
+            .with("Beams", engine.beams().len())
+            .with("Splits", stats.splits)
+            .with("Merged", stats.merged)
+            .with("Exits", stats.exits)
+            .with("Visited", engine.visited_count())
     }
 }
 
@@ -108,12 +68,19 @@ impl From<&str> for TachyonManifold {
 fn main() {
     env_logger::builder().format_timestamp(None).init();
 
-    let mut manifold: TachyonManifold = INPUT.into();
+    let manifold: TachyonManifold = INPUT.into();
+    let mut engine = manifold.beams();
+    let mut debugger = Debugger::new().with_delay(DELAY).running();
 
-    while manifold.next() {
-        manifold.print();
-        thread::sleep(Duration::from_millis(50));
+    while engine.step() {
+        // Tracing copies the whole grid, so only do it when there is a screen to show it on
+        if debugger.is_attached() {
+            debugger.frame(&engine.trace(BEAM), TachyonManifold::vars(&engine));
+        }
     }
+    debugger.finish();
 
-    println!("Answer: {}", manifold.num_splits);
+    let stats = engine.stats();
+    log::debug!("{:?}", stats);
+    println!("Answer: {}", stats.splits);
 }
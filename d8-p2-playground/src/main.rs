@@ -3,7 +3,7 @@ use std::{
     fmt::{self, Display, Formatter},
 };
 
-use aoc25::math::{self, Point3i64};
+use aoc25::math::{self, Point3i64, exact::Exact};
 use itertools::Itertools;
 use log::debug;
 
@@ -73,7 +73,7 @@ fn main() {
     let distances = find_closest_junction_boxes(&junction_boxes);
     let mut circuits: Vec<Circuit> = vec![];
     let mut circuit_map: HashMap<JunctionBox, usize> = HashMap::default();
-    let mut wall_distance: Option<usize> = None;
+    let mut wall_distance: Option<Exact> = None;
 
     'outer: for (i, j, distance) in distances.iter() {
         let value_a = junction_boxes[*i];
@@ -131,7 +131,7 @@ fn main() {
             debug!("Single-circuit achieved");
             let ax = value_a.0;
             let bx = value_b.0;
-            wall_distance = Some(ax * bx);
+            wall_distance = Some(Exact::from(ax) * Exact::from(bx));
             break 'outer;
         }
 
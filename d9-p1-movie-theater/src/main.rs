@@ -4,11 +4,15 @@ use std::{
     collections::HashSet,
     iter,
     ops::Coroutine,
-    thread,
     time::{Duration, Instant},
 };
 
-use aoc25::{char_grid::CharGrid, io, range::bidirectional_range, util::string::format_duration};
+use aoc25::{
+    char_grid::CharGrid,
+    debugger::{Debugger, Vars},
+    io,
+    util::string::format_duration,
+};
 
 #[cfg(debug_assertions)]
 const INPUT: &str = include_str!("../input_example.txt");
@@ -17,6 +21,7 @@ const INPUT: &str = include_str!("../input.txt");
 const TILE_RED: char = '#';
 const TILE_OTHER: char = '.';
 const TILE_PAINTED: char = 'o';
+const DELAY: Duration = Duration::from_millis(200);
 
 #[derive(Default, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 struct MovieTheater {
@@ -78,15 +83,8 @@ impl MovieTheater {
     }
 
     pub fn draw_rect(&mut self, rect: Rect) {
-        let (x1, y1, x2, y2) = (rect.0 as i64, rect.1 as i64, rect.2 as i64, rect.3 as i64);
-        let dx = x2 - x1;
-        let dy = y2 - y1;
-
-        for x in bidirectional_range(x1, dx) {
-            for y in bidirectional_range(y1, dy) {
-                self.grid.set(x as usize, y as usize, TILE_PAINTED);
-            }
-        }
+        let (x1, y1, x2, y2) = rect;
+        self.grid.fill_rect((x1, y1), (x2, y2), TILE_PAINTED);
     }
 
     #[cfg(debug_assertions)]
@@ -128,6 +126,11 @@ fn main() {
     let start = Instant::now();
     let num_pairs = positions.len() * (positions.len() - 1) / 2;
     let mut pairs_tested: HashSet<Rect> = HashSet::default();
+    let mut debugger = if cfg!(debug_assertions) {
+        Debugger::new().with_delay(DELAY)
+    } else {
+        Debugger::detached()
+    };
 
     for (tile_x, tile_y) in positions {
         let corners: Vec<_> =
@@ -154,22 +157,30 @@ fn main() {
                 theater.draw_rect(rect);
             }
 
-            render(Context {
-                theater: &theater,
-                tile_x,
-                tile_y,
-                corner_x,
-                corner_y,
-                width,
-                height,
-                area,
-                max_area,
-                start,
-                pairs_tested: pairs_tested.len(),
-                num_pairs,
-            })
+            render(
+                Context {
+                    theater: &theater,
+                    tile_x,
+                    tile_y,
+                    corner_x,
+                    corner_y,
+                    width,
+                    height,
+                    area,
+                    max_area,
+                    start,
+                    pairs_tested: pairs_tested.len(),
+                    num_pairs,
+                },
+                &mut debugger,
+            )
         }
     }
+
+    if debugger.is_attached() {
+        debugger.finish();
+        println!("Max: {}", max_area);
+    }
 }
 
 struct Context<'a> {
@@ -187,9 +198,25 @@ struct Context<'a> {
     num_pairs: usize,
 }
 
-fn render(context: Context) {
-    if cfg!(debug_assertions) {
-        thread::sleep(Duration::from_millis(200));
+impl Context<'_> {
+    fn vars(&self) -> Vars {
+        Vars::new()
+            .with("Tile", format!("({}, {})", self.tile_x, self.tile_y))
+            .with("Corner", format!("({}, {})", self.corner_x, self.corner_y))
+            .with("Size", format!("{} x {}", self.width, self.height))
+            .with("Area", self.area)
+            .with("Max", self.max_area)
+            .with("Tested", self.pairs_tested)
+            .with("Pairs", self.num_pairs)
+            .with("Runtime", format_duration(self.start.elapsed()))
+    }
+}
+
+/// Hands the frame to the debugger when it is attached, and prints it otherwise.
+fn render(context: Context, debugger: &mut Debugger) {
+    if debugger.is_attached() {
+        debugger.frame(&context.theater.grid, context.vars());
+        return;
     }
 
     io::clear_screen();
@@ -1,17 +1,18 @@
-#![feature(coroutines, coroutine_trait, iter_from_coroutine)]
+#![feature(coroutines, iter_from_coroutine)]
 
 use std::{
     fmt::{self, Display, Formatter},
     fs, iter,
     path::PathBuf,
-    thread,
     time::{Duration, Instant},
 };
 
 use aoc25::{
-    char_grid::CharGrid,
-    io::{self, ReadProgress},
-    range::bidirectional_range,
+    cache::Cache,
+    char_grid::{CharGrid, draw::rect_outline_iter, raycast::RayOutcome, region::Connectivity},
+    debugger::{Debugger, Vars},
+    direction::Direction,
+    io::{self, LineReader, ReadProgress},
     util::string::{format_duration, format_mem_size},
 };
 use clap::Parser;
@@ -29,8 +30,10 @@ const TILE_RED: char = '#';
 const TILE_GREEN: char = 'X';
 const TILE_OTHER: char = '.';
 const TILE_PAINTED: char = 'o';
-const DIRECTIONS: [(i64, i64); 4] = [(-1, 0), (0, -1), (1, 0), (0, 1)];
+const DIRECTIONS: [Direction; 4] = Direction::Left.clockwise_from();
 const DELAY: Duration = Duration::from_millis(0);
+const CACHE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/.cache");
+const CACHE_VERSION: &str = "1";
 
 #[derive(Default, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 struct MovieTheater {
@@ -90,7 +93,8 @@ impl MovieTheater {
     fn draw_green_tile_border(grid: &mut CharGrid, red_tile_positions: &[(usize, usize)]) {
         println!("Drawing green border tiles...");
 
-        let mut visited: IndexMap<(usize, usize), usize> = IndexMap::default();
+        // Each connected corner, with the direction number it left in and the last border tile
+        let mut visited: IndexMap<(usize, usize), (usize, (usize, usize))> = IndexMap::default();
         let first = red_tile_positions[0] as (usize, usize);
         let (mut corner_x, mut corner_y) = first as (usize, usize);
         let (first_x, first_y) = first;
@@ -99,45 +103,31 @@ impl MovieTheater {
 
         while visited.len() < red_tile_positions.len() {
             while direction_index < DIRECTIONS.len() {
-                let (dx, dy) = DIRECTIONS[direction_index];
-                let start_x = corner_x as i64 + dx;
-                let start_y = corner_y as i64 + dy;
-
-                if !grid.contains(start_x, start_y) {
+                let direction = DIRECTIONS[direction_index];
+                let Some((start_x, start_y)) = grid.step((corner_x, corner_y), direction) else {
                     // Cannot go this direction
                     direction_index += 1;
                     continue;
-                }
+                };
 
                 // Try and find a connected corner
-                let cast: Vec<_> = grid
-                    .raycast_iter(
-                        start_x as usize,
-                        start_y as usize,
-                        dx,
-                        dy,
-                        &[TILE_RED, TILE_GREEN],
-                    )
-                    .collect();
-
-                let (last_x, last_y) = match cast.last() {
-                    Some(last) => *last,
-                    None => {
-                        // There is a red or green tile directly adjacent
-                        direction_index += 1;
-                        continue;
-                    }
-                };
+                let ray = grid.raycast((start_x, start_y), direction, &[TILE_RED, TILE_GREEN]);
 
-                let (hit_x, hit_y) = (last_x as i64 + dx, last_y as i64 + dy);
+                let Some(last) = ray.last() else {
+                    // There is a red or green tile directly adjacent
+                    direction_index += 1;
+                    continue;
+                };
 
-                if !grid.contains(hit_x, hit_y) {
+                let RayOutcome::Hit {
+                    pos: (hit_x, hit_y),
+                    char: hit_ch,
+                } = ray.outcome
+                else {
                     // We did not find a red or green tile
                     direction_index += 1;
                     continue;
-                }
-
-                let hit_ch = grid.get(hit_x as usize, hit_y as usize);
+                };
 
                 if hit_ch == TILE_GREEN {
                     // Intersects another border
@@ -145,24 +135,19 @@ impl MovieTheater {
                     continue;
                 }
 
-                if (hit_x != first_x as i64 || hit_y != first_y as i64)
-                    && visited.contains_key(&(hit_x as usize, hit_y as usize))
-                {
+                if (hit_x, hit_y) != (first_x, first_y) && visited.contains_key(&(hit_x, hit_y)) {
                     // The hit corner is already connected to another node, and it is not the first
                     // corner which would complete the border.
                     direction_index += 1;
                     continue;
                 }
 
-                visited.insert((corner_x, corner_y), direction_index + 1);
-
-                for (border_x, border_y) in cast {
-                    grid.set(border_x, border_y, TILE_GREEN);
-                }
+                visited.insert((corner_x, corner_y), (direction_index + 1, last));
+                grid.draw_line((start_x, start_y), last, TILE_GREEN);
 
                 found_connection = true;
                 direction_index = 0;
-                (corner_x, corner_y) = (hit_x as usize, hit_y as usize);
+                (corner_x, corner_y) = (hit_x, hit_y);
             }
 
             if !found_connection {
@@ -171,37 +156,28 @@ impl MovieTheater {
                 // If the visited list is empty, we could not find a connection from the first node
                 let mut pop_visited = || {
                     let last = visited.pop().expect("nowhere to go");
-                    let ((last_x, last_y), last_direction) = last;
-                    let (dx, dy) = DIRECTIONS[last_direction - 1];
+                    let (last_corner, (last_direction, last_border)) = last;
+                    let direction = DIRECTIONS[last_direction - 1];
+                    let border_start = grid
+                        .step(last_corner, direction)
+                        .expect("border starts next to its corner");
 
                     // Undo border
-                    let cast: Vec<_> = grid
-                        .raycast_iter(
-                            (last_x as i64 + dx) as usize,
-                            (last_y as i64 + dy) as usize,
-                            dx,
-                            dy,
-                            &[TILE_RED],
-                        )
-                        .collect();
-
-                    for (border_x, border_y) in cast {
-                        grid.set(border_x, border_y, TILE_OTHER);
-                    }
+                    grid.draw_line(border_start, last_border, TILE_OTHER);
 
                     last
                 };
 
                 let mut last = pop_visited();
 
-                while last.1 == DIRECTIONS.len() {
+                while last.1.0 == DIRECTIONS.len() {
                     // We have exhausted all directions for the last node
                     // Backtrack until there is still directions to test
                     last = pop_visited();
                 }
 
                 // Resume from the backtracked node
-                let (last_corner, last_direction) = last;
+                let (last_corner, (last_direction, _)) = last;
                 direction_index = last_direction;
                 (corner_x, corner_y) = last_corner;
             }
@@ -211,344 +187,25 @@ impl MovieTheater {
     }
 
     fn fill_green_tiles(grid: &mut CharGrid) {
-        io::hide_cursor();
-
-        let grid_width = grid.width();
-        let grid_height = grid.height();
-        let (mut start_x, mut start_y) = (0, 0);
+        println!("Drawing green fill tiles...");
 
         debug_grid(grid);
 
-        // Find starting position
-        for (x, y) in grid.coordinates_iter() {
-            let current = grid.get(x, y);
+        // Empty regions that do not reach the edge of the grid are enclosed by the border
+        let regions = grid.label_regions(Connectivity::Four, |ch| ch == TILE_OTHER);
 
-            if current != TILE_GREEN {
+        for region in regions.iter() {
+            if regions.touches_edge(region.id) {
                 continue;
             }
 
-            let next_x = x + 1;
-
-            if grid.contains(next_x as i64, y as i64) && grid.get(next_x, y) == TILE_OTHER {
-                // Green tile followed by empty, found starting position
-                (start_x, start_y) = (next_x, y);
-                break;
-            }
-        }
-
-        let (mut x, mut y) = (start_x, start_y);
-
-        loop {
-            let row = y + 1;
-            let progress = row as f64 / grid_height as f64 * 100.0;
-
-            debug_grid(grid);
-
-            print!(
-                "\rDrawing green fill tiles... {:.2}% ({}/{})",
-                progress, row, grid_height,
+            grid.fill(
+                region.start,
+                Connectivity::Four,
+                |ch| ch == TILE_OTHER,
+                TILE_GREEN,
             );
-
-            if y > grid_height {
-                break;
-            }
-
-            if x > grid_width - 1 {
-                x = 0;
-                y += 1;
-                continue;
-            }
-
-            // Fill right until we hit a border
-            let mut current = grid.get(x, y);
-
-            if current == TILE_OTHER {
-                grid.set(x, y, TILE_GREEN);
-                x += 1;
-                continue;
-            }
-
-            let rewind = |current: &mut char, x: &mut usize, y: &mut usize| {
-                let mut scan_x = *x - 1;
-                let mut along_edge = false;
-                let mut outside = false;
-
-                loop {
-                    if scan_x < 1 {
-                        // Reached end of grid
-                        break;
-                    }
-
-                    let scan = grid.get(scan_x, *y);
-                    let left = grid.get(scan_x - 1, *y);
-                    let right = grid.get(scan_x + 1, *y);
-
-                    if !outside && !along_edge && scan != TILE_OTHER && left == TILE_OTHER {
-                        // Reached a border from inside
-                        // Move position to one after border
-                        *x = scan_x + 1;
-                        scan_x -= 1;
-                        outside = true;
-                        continue;
-                    }
-
-                    if outside && scan == TILE_OTHER {
-                        // Space outside
-                        scan_x -= 1;
-                        continue;
-                    }
-
-                    if outside && right == TILE_OTHER && scan == TILE_GREEN {
-                        // Reached a border from outside
-                        // Move position to one before border
-                        *x = scan_x - 1;
-                        scan_x -= 1;
-                        outside = false;
-                        continue;
-                    }
-
-                    if !along_edge && scan == TILE_RED {
-                        // Encountered a corner from inside
-                        along_edge = true;
-                        outside = false;
-                        scan_x -= 1;
-                        continue;
-                    }
-
-                    if along_edge {
-                        if scan == TILE_RED {
-                            scan_x -= 1;
-                            let mut inside = true;
-
-                            // Reached the end of an edge
-                            for (dx, dy) in [(0, -1), (-1, 0), (0, 1)] {
-                                // Check up, left, and down for a border
-                                let cast_x = scan_x as i64 + dx;
-                                let cast_y = *y as i64 + dy;
-
-                                if !grid.contains(cast_x, cast_y) {
-                                    inside = false;
-                                    break;
-                                }
-
-                                if grid.get(cast_x as usize, cast_y as usize) != TILE_OTHER {
-                                    continue;
-                                }
-
-                                let cast = grid.raycast_iter(
-                                    cast_x as usize,
-                                    cast_y as usize,
-                                    dx,
-                                    dy,
-                                    &[TILE_RED, TILE_GREEN],
-                                );
-
-                                let (last_x, last_y) = match cast.last() {
-                                    Some(last) => last,
-                                    None => {
-                                        // Border not found for current direction
-                                        inside = false;
-                                        break;
-                                    }
-                                };
-
-                                let (hit_x, hit_y) = (last_x as i64 + dx, last_y as i64 + dy);
-
-                                if !grid.contains(hit_x, hit_y) {
-                                    // Reached end of grid
-                                    inside = false;
-                                    break;
-                                }
-                            }
-
-                            along_edge = false;
-
-                            if inside {
-                                *x = scan_x;
-                                continue;
-                            }
-
-                            outside = true;
-                        }
-
-                        scan_x -= 1;
-                        continue;
-                    }
-
-                    scan_x -= 1;
-                    *x = scan_x;
-                }
-
-                *current = grid.get(*x, *y);
-            };
-
-            let advance = |x: &mut usize, y: &mut usize| {
-                let mut scan_x = *x;
-                let mut area_found = false;
-                let mut along_edge = false;
-                let mut outside = false;
-
-                // Try to jump to a next area
-                loop {
-                    if scan_x > grid_width - 2 {
-                        // No room for another area
-                        break;
-                    }
-
-                    let scan = grid.get(scan_x, *y);
-                    let right = grid.get(scan_x + 1, *y);
-                    let left = grid.get(scan_x - 1, *y);
-
-                    if !outside && !along_edge && scan != TILE_OTHER && right == TILE_OTHER {
-                        // Reached a border from inside
-                        scan_x += 1;
-                        outside = true;
-                        continue;
-                    }
-
-                    if outside && scan == TILE_OTHER {
-                        // Space outside
-                        scan_x += 1;
-                        continue;
-                    }
-
-                    if outside && scan == TILE_GREEN && left == TILE_OTHER {
-                        // Reached a border from outside
-                        scan_x += 1;
-                        *x = scan_x;
-                        area_found = true;
-                        break;
-                    }
-
-                    if !along_edge && scan == TILE_RED {
-                        // Encountered a corner from inside
-                        along_edge = true;
-                        outside = false;
-                        scan_x += 1;
-                        continue;
-                    }
-
-                    if along_edge {
-                        if scan == TILE_RED {
-                            scan_x += 1;
-                            let mut inside = true;
-
-                            // Reached the end of an edge
-                            // Check up, right, and down for a border
-                            for (dx, dy) in [(0, -1), (1, 0), (0, 1)] {
-                                let cast_x = scan_x as i64 + dx;
-                                let cast_y = *y as i64 + dy;
-
-                                if !grid.contains(cast_x, cast_y) {
-                                    inside = false;
-                                    break;
-                                }
-
-                                if grid.get(cast_x as usize, cast_y as usize) != TILE_OTHER {
-                                    continue;
-                                }
-
-                                let cast = grid.raycast_iter(
-                                    cast_x as usize,
-                                    cast_y as usize,
-                                    dx,
-                                    dy,
-                                    &[TILE_RED, TILE_GREEN],
-                                );
-
-                                let (last_x, last_y) = match cast.last() {
-                                    Some(last) => last,
-                                    None => {
-                                        // Border not found for current direction
-                                        inside = false;
-                                        break;
-                                    }
-                                };
-
-                                let (hit_x, hit_y) = (last_x as i64 + dx, last_y as i64 + dy);
-
-                                if !grid.contains(hit_x, hit_y) {
-                                    // Reached end of grid
-                                    inside = false;
-                                    break;
-                                }
-                            }
-
-                            along_edge = false;
-
-                            if inside {
-                                *x = scan_x;
-                                area_found = true;
-                                break;
-                            }
-
-                            outside = true;
-                        }
-                        scan_x += 1;
-                        continue;
-                    }
-
-                    scan_x += 1;
-                    *x = scan_x;
-                }
-
-                area_found
-            };
-
-            if advance(&mut x, &mut y) {
-                continue;
-            }
-
-            // Rewind left
-            rewind(&mut current, &mut x, &mut y);
-
-            if y == grid_height - 2 {
-                break;
-            }
-
-            let mut finished = false;
-
-            // Find path downward
-            loop {
-                let below = grid.get(x, y + 1);
-                let current = grid.get(x, y);
-
-                if current == TILE_OTHER {
-                    if advance(&mut x, &mut y) {
-                        continue;
-                    }
-                    break;
-                }
-
-                if below == TILE_OTHER {
-                    break;
-                }
-
-                x += 1;
-
-                if x == grid_width - 2 {
-                    finished = true;
-                    break;
-                }
-            }
-
-            if finished {
-                break;
-            }
-
-            y += 1;
-
-            let left = grid.get(x - 1, y);
-
-            if left != TILE_OTHER {
-                continue;
-            }
-
-            rewind(&mut current, &mut x, &mut y);
         }
-
-        io::show_cursor();
-        println!();
     }
 
     pub fn opposite_corners_iter(
@@ -573,44 +230,15 @@ impl MovieTheater {
     }
 
     pub fn draw_rect(&mut self, rect: Rect) {
-        let (x1, y1, x2, y2) = (rect.0 as i64, rect.1 as i64, rect.2 as i64, rect.3 as i64);
-        let dx = x2 - x1;
-        let dy = y2 - y1;
-
-        for x in bidirectional_range(x1, dx) {
-            for y in bidirectional_range(y1, dy) {
-                self.grid.set(x as usize, y as usize, TILE_PAINTED);
-            }
-        }
+        let Rect(x1, y1, x2, y2) = rect;
+        self.grid.fill_rect((x1, y1), (x2, y2), TILE_PAINTED);
     }
 
     pub fn is_rect_valid(&self, rect: Rect) -> bool {
-        let (x1, y1, x2, y2) = (rect.0 as i64, rect.1 as i64, rect.2 as i64, rect.3 as i64);
-        let dx = x2 - x1;
-        let dy = y2 - y1;
+        let Rect(x1, y1, x2, y2) = rect;
 
         // Check each edge
-        for x in bidirectional_range(x1, dx) {
-            if self.initial().get(x as usize, y1 as usize) == TILE_OTHER {
-                return false;
-            }
-
-            if self.initial().get(x as usize, y2 as usize) == TILE_OTHER {
-                return false;
-            }
-        }
-
-        for y in bidirectional_range(y1, dy) {
-            if self.initial().get(x1 as usize, y as usize) == TILE_OTHER {
-                return false;
-            }
-
-            if self.initial().get(x2 as usize, y as usize) == TILE_OTHER {
-                return false;
-            }
-        }
-
-        true
+        rect_outline_iter((x1, y1), (x2, y2)).all(|(x, y)| self.initial().get(x, y) != TILE_OTHER)
     }
 
     #[cfg(debug_assertions)]
@@ -689,6 +317,11 @@ fn main() {
     let start = Instant::now();
     let num_pairs = positions.len() * (positions.len() - 1) / 2;
     let mut pairs_tested = 0;
+    let mut debugger = if cfg!(debug_assertions) {
+        Debugger::new().with_delay(DELAY)
+    } else {
+        Debugger::detached()
+    };
 
     debug_grid(&theater.grid);
 
@@ -722,24 +355,32 @@ fn main() {
 
             pairs_tested += 1;
 
-            render(Context {
-                theater: &theater,
-                tile_x,
-                tile_y,
-                corner_x,
-                corner_y,
-                width,
-                height,
-                area,
-                max_area,
-                max_position,
-                start,
-                pairs_tested,
-                num_pairs,
-                valid,
-            })
+            render(
+                Context {
+                    theater: &theater,
+                    tile_x,
+                    tile_y,
+                    corner_x,
+                    corner_y,
+                    width,
+                    height,
+                    area,
+                    max_area,
+                    max_position,
+                    start,
+                    pairs_tested,
+                    num_pairs,
+                    valid,
+                },
+                &mut debugger,
+            )
         }
     }
+
+    if debugger.is_attached() {
+        debugger.finish();
+        println!("Max: {}@{}", max_area, max_position);
+    }
 }
 
 fn load_theater(bake: &Option<PathBuf>, input: &Option<PathBuf>) -> MovieTheater {
@@ -753,16 +394,21 @@ fn load_theater(bake: &Option<PathBuf>, input: &Option<PathBuf>) -> MovieTheater
 
     if let Some(input) = input {
         io::hide_cursor();
-        let baked_input: String = io::read_to_string_with_progress(input, read_input_progress)
+        let grid = LineReader::open(input)
+            .and_then(|lines| lines.on_progress(read_input_progress).read_char_grid())
             .expect("could not read baked input");
         io::show_cursor();
         println!();
-        println!("Parsing grid...");
-        let grid: CharGrid = baked_input.as_str().into();
         return grid.into();
     }
 
-    INPUT.into()
+    let grid = Cache::new(CACHE_DIR)
+        .get_or_compute("filled-grid", CACHE_VERSION, INPUT, || {
+            MovieTheater::from(INPUT).grid
+        })
+        .expect("could not access cache");
+
+    grid.into()
 }
 
 fn read_input_progress(progress: ReadProgress) {
@@ -792,15 +438,32 @@ struct Context<'a> {
     valid: bool,
 }
 
-fn render(context: Context) {
-    if cfg!(debug_assertions) {
-        thread::sleep(DELAY);
+impl Context<'_> {
+    fn vars(&self) -> Vars {
+        Vars::new()
+            .with("Tile", format!("({}, {})", self.tile_x, self.tile_y))
+            .with("Corner", format!("({}, {})", self.corner_x, self.corner_y))
+            .with("Size", format!("{} x {}", self.width, self.height))
+            .with("Area", self.area)
+            .with("Valid", self.valid)
+            .with("Max", self.max_area)
+            .with("Max Rect", self.max_position)
+            .with("Tested", self.pairs_tested)
+            .with("Pairs", self.num_pairs)
+            .with("Runtime", format_duration(self.start.elapsed()))
+    }
+}
+
+/// Hands the frame to the debugger when it is attached, and prints it otherwise.
+fn render(context: Context, debugger: &mut Debugger) {
+    if debugger.is_attached() {
+        debugger.frame(&context.theater.grid, context.vars());
+        return;
     }
 
     io::clear_screen();
 
-    debug_grid(context.theater.initial());
-    debug_grid(&context.theater.grid);
+    debug_diff(context.theater.initial(), &context.theater.grid);
 
     let pairs_tested = context.pairs_tested;
     let num_pairs = context.num_pairs;
@@ -831,3 +494,12 @@ fn debug_grid(grid: &CharGrid) {
 
 #[cfg(not(debug_assertions))]
 fn debug_grid(_grid: &CharGrid) {}
+
+/// Shows the tiles painted since `old` highlighted on top of `new`.
+#[cfg(debug_assertions)]
+fn debug_diff(old: &CharGrid, new: &CharGrid) {
+    println!("{}", old.diff(new).render_overlay());
+}
+
+#[cfg(not(debug_assertions))]
+fn debug_diff(_old: &CharGrid, _new: &CharGrid) {}